@@ -0,0 +1,109 @@
+use crate::daterange::DateRange;
+use crate::datetimerange::DateTimeRange;
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+
+/// A rule that produces the instants at which a `DateTimeRange` should be split.
+pub trait BoundaryRule {
+    /// Get the boundaries for the range. Boundaries outside the range are ignored
+    /// by `DateTimeRange::split_by`, so rules don't need to filter them out.
+    fn boundaries(&self, range: &DateTimeRange) -> Vec<NaiveDateTime>;
+}
+
+/// Boundaries every N minutes, aligned to midnight of the range's start date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinuteIntervalBoundaries {
+    minutes: i64,
+}
+
+impl MinuteIntervalBoundaries {
+    /// Create boundaries every `minutes` minutes, or `None` if `minutes` is zero.
+    pub fn of(minutes: u32) -> Option<Self> {
+        if minutes == 0 {
+            return None;
+        }
+        Some(Self { minutes: minutes as i64 })
+    }
+
+    /// Create boundaries at the top of every hour.
+    pub fn hourly() -> Self {
+        Self { minutes: 60 }
+    }
+}
+
+impl BoundaryRule for MinuteIntervalBoundaries {
+    fn boundaries(&self, range: &DateTimeRange) -> Vec<NaiveDateTime> {
+        let origin = range.start().date().and_time(NaiveTime::MIN);
+        let step = Duration::minutes(self.minutes);
+        let periods_before_start = (range.start() - origin).num_minutes() / self.minutes;
+
+        let mut boundaries = Vec::new();
+        let mut current = origin + Duration::minutes(periods_before_start * self.minutes);
+        while current < range.end() {
+            boundaries.push(current);
+            current += step;
+        }
+        boundaries
+    }
+}
+
+/// Boundaries at the same times of day on every day, e.g. daypart edges or a
+/// night differential starting at 22:00.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyTimeBoundaries {
+    times: Vec<NaiveTime>,
+}
+
+impl DailyTimeBoundaries {
+    pub fn of(times: Vec<NaiveTime>) -> Self {
+        Self { times }
+    }
+
+    pub fn times(&self) -> &[NaiveTime] {
+        &self.times
+    }
+}
+
+impl BoundaryRule for DailyTimeBoundaries {
+    fn boundaries(&self, range: &DateTimeRange) -> Vec<NaiveDateTime> {
+        let mut boundaries = Vec::new();
+        let mut date = range.start().date();
+        while date <= range.end().date() {
+            boundaries.extend(self.times.iter().map(|time| date.and_time(*time)));
+            date += Duration::days(1);
+        }
+        boundaries
+    }
+}
+
+/// Boundaries at the end of each period of a `DateRange` series (e.g. pay periods).
+/// The boundary is midnight at the start of the day after each period's end date.
+#[derive(Debug, Clone, Copy)]
+pub struct PeriodEndBoundaries {
+    date_range: DateRange,
+}
+
+impl PeriodEndBoundaries {
+    pub fn of(date_range: DateRange) -> Self {
+        Self { date_range }
+    }
+
+    pub fn date_range(&self) -> &DateRange {
+        &self.date_range
+    }
+}
+
+impl BoundaryRule for PeriodEndBoundaries {
+    fn boundaries(&self, range: &DateTimeRange) -> Vec<NaiveDateTime> {
+        let mut boundaries = Vec::new();
+        let mut period = self.date_range.range_containing_date(range.start().date());
+        loop {
+            let boundary = (period.end_date() + Duration::days(1)).and_time(NaiveTime::MIN);
+            if boundary >= range.end() {
+                break;
+            }
+            boundaries.push(boundary);
+            period = period.next();
+        }
+        boundaries
+    }
+}
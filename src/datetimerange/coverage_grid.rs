@@ -0,0 +1,365 @@
+use crate::daterange::DateRange;
+use crate::datetimerange::{DateTimeRange, DateTimeRangeWithPeriodLength, PeriodLength};
+use chrono::{NaiveDate, NaiveTime};
+use std::error::Error;
+use std::fmt;
+
+const EPSILON: f64 = 1e-9;
+
+/// How shifts are counted in a `CoverageGrid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoverageMode {
+    /// Every period a shift touches counts as one person.
+    #[default]
+    Headcount,
+    /// Periods count the fraction of the period the shift covers.
+    Weighted,
+}
+
+/// Whether an interval has more or less coverage than required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaffingStatus {
+    Over,
+    Under,
+}
+
+/// A run of consecutive periods that are all over or all under the required coverage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaffingInterval {
+    range: DateTimeRange,
+    first_index: usize,
+    last_index: usize,
+    status: StaffingStatus,
+    peak_difference: f64,
+}
+
+impl StaffingInterval {
+    /// Get the time covered by the interval.
+    pub fn range(&self) -> &DateTimeRange {
+        &self.range
+    }
+
+    /// Get the grid indexes of the first and last periods in the interval.
+    pub fn index_range(&self) -> (usize, usize) {
+        (self.first_index, self.last_index)
+    }
+
+    pub fn status(&self) -> StaffingStatus {
+        self.status
+    }
+
+    /// Get the largest difference from the required coverage in the interval.
+    /// Positive when over-staffed, negative when under-staffed.
+    pub fn peak_difference(&self) -> f64 {
+        self.peak_difference
+    }
+}
+
+/// Errors from combining shifts and grids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageGridError {
+    /// The shift or grid uses a different period length.
+    PeriodLengthMismatch,
+    /// The grids don't start on the same date or don't have the same number of periods.
+    ShapeMismatch,
+    /// The shift's periods are indexed in a zone, but the grid is indexed by wall-clock time.
+    ZonedShift,
+}
+
+impl fmt::Display for CoverageGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoverageGridError::PeriodLengthMismatch => write!(f, "period lengths do not match"),
+            CoverageGridError::ShapeMismatch => write!(f, "grids do not cover the same periods"),
+            CoverageGridError::ZonedShift => write!(f, "zoned shifts cannot be added to a wall-clock grid"),
+        }
+    }
+}
+
+impl Error for CoverageGridError {}
+
+/// Coverage per period, accumulated from many shifts, over one or more days.
+///
+/// Periods are indexed by wall-clock time from midnight of the start date, so index 0 is
+/// the first period of the start date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageGrid {
+    start_date: NaiveDate,
+    period_length: PeriodLength,
+    mode: CoverageMode,
+    values: Vec<f64>,
+}
+
+impl CoverageGrid {
+    /// Create an empty grid covering a single date.
+    pub fn for_date(date: NaiveDate, period_length: PeriodLength, mode: CoverageMode) -> Self {
+        Self::for_date_range(&DateRange::new(date, date), period_length, mode)
+    }
+
+    /// Create an empty grid covering every date in the range.
+    pub fn for_date_range(date_range: &DateRange, period_length: PeriodLength, mode: CoverageMode) -> Self {
+        let len = date_range.len() * period_length.periods_per_day() as usize;
+        Self {
+            start_date: date_range.start_date(),
+            period_length,
+            mode,
+            values: vec![0.0; len],
+        }
+    }
+
+    /// Create a grid from existing values, e.g. a required-staffing curve.
+    pub fn from_values(start_date: NaiveDate, period_length: PeriodLength, values: Vec<f64>) -> Self {
+        Self {
+            start_date,
+            period_length,
+            mode: CoverageMode::Weighted,
+            values,
+        }
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn period_length(&self) -> PeriodLength {
+        self.period_length
+    }
+
+    pub fn mode(&self) -> CoverageMode {
+        self.mode
+    }
+
+    /// Get the coverage for each period.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Get the coverage for the period at the index.
+    pub fn value_at(&self, index: usize) -> Option<f64> {
+        self.values.get(index).copied()
+    }
+
+    /// Get the number of periods in the grid.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Get the time covered by the period at the index.
+    pub fn period_range(&self, index: usize) -> DateTimeRange {
+        let origin = self.start_date.and_time(NaiveTime::MIN);
+        let length = self.period_length.as_duration();
+        let start = origin + length * index as i32;
+        DateTimeRange::of(start, start + length)
+    }
+
+    /// Add a shift with a weight of one. Periods outside the grid are ignored.
+    pub fn add(&mut self, shift: &DateTimeRange) {
+        self.add_weighted(shift, 1.0);
+    }
+
+    /// Add a shift counted with the weight, e.g. 0.5 for a trainee.
+    /// Periods outside the grid are ignored.
+    pub fn add_weighted(&mut self, shift: &DateTimeRange, weight: f64) {
+        let shift = DateTimeRangeWithPeriodLength::of(shift.clone(), self.period_length);
+        let day_offset = (shift.date_time_range().start().date() - self.start_date).num_days()
+            * self.period_length.periods_per_day() as i64;
+
+        for (index, fraction) in shift.coverage() {
+            let index = day_offset + index as i64;
+            if index < 0 || index >= self.values.len() as i64 {
+                continue;
+            }
+
+            self.values[index as usize] += match self.mode {
+                CoverageMode::Headcount => weight,
+                CoverageMode::Weighted => weight * fraction,
+            };
+        }
+    }
+
+    /// Add a shift that is already on a period grid. Its period length must match the grid's,
+    /// and it must not have a zone, since the grid's periods are wall-clock periods.
+    pub fn add_shift(&mut self, shift: &DateTimeRangeWithPeriodLength) -> Result<(), CoverageGridError> {
+        if shift.period_length() != self.period_length {
+            return Err(CoverageGridError::PeriodLengthMismatch);
+        }
+        if shift.zone().is_some() {
+            return Err(CoverageGridError::ZonedShift);
+        }
+        self.add(shift.date_time_range());
+        Ok(())
+    }
+
+    /// Subtract another grid, e.g. a required-staffing curve, period by period.
+    /// Positive values in the result are over-staffed and negative values under-staffed.
+    pub fn subtract(&self, required: &CoverageGrid) -> Result<CoverageGrid, CoverageGridError> {
+        self.check_same_shape(required)?;
+        Ok(Self {
+            values: self.values.iter().zip(&required.values).map(|(a, b)| a - b).collect(),
+            ..self.clone()
+        })
+    }
+
+    /// Get the runs of consecutive periods that are over or under the required coverage.
+    pub fn staffing_intervals(&self, required: &CoverageGrid) -> Result<Vec<StaffingInterval>, CoverageGridError> {
+        let difference = self.subtract(required)?;
+        let status_of = |value: f64| {
+            if value > EPSILON {
+                Some(StaffingStatus::Over)
+            } else if value < -EPSILON {
+                Some(StaffingStatus::Under)
+            } else {
+                None
+            }
+        };
+
+        let mut intervals: Vec<StaffingInterval> = Vec::new();
+        for (index, value) in difference.values.iter().copied().enumerate() {
+            let status = match status_of(value) {
+                Some(status) => status,
+                None => continue,
+            };
+
+            match intervals.last_mut() {
+                Some(last) if last.status == status && last.last_index + 1 == index => {
+                    last.last_index = index;
+                    last.range = DateTimeRange::of(last.range.start(), difference.period_range(index).end());
+                    if value.abs() > last.peak_difference.abs() {
+                        last.peak_difference = value;
+                    }
+                }
+                _ => intervals.push(StaffingInterval {
+                    range: difference.period_range(index),
+                    first_index: index,
+                    last_index: index,
+                    status,
+                    peak_difference: value,
+                }),
+            }
+        }
+        Ok(intervals)
+    }
+
+    fn check_same_shape(&self, other: &CoverageGrid) -> Result<(), CoverageGridError> {
+        if self.period_length != other.period_length {
+            Err(CoverageGridError::PeriodLengthMismatch)
+        } else if self.start_date != other.start_date || self.values.len() != other.values.len() {
+            Err(CoverageGridError::ShapeMismatch)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timezone::{LocalTimePolicy, Zone};
+    use rstest::rstest;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, day).unwrap()
+    }
+
+    fn shift(start: (u32, u32), end: (u32, u32)) -> DateTimeRange {
+        DateTimeRange::from_time_range_on_date(
+            NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+            date(20),
+        )
+    }
+
+    fn hourly_grid(mode: CoverageMode) -> CoverageGrid {
+        let mut grid = CoverageGrid::for_date(date(20), PeriodLength::SIXTY_MINUTES, mode);
+        grid.add(&shift((9, 0), (17, 0)));
+        grid.add(&shift((12, 30), (20, 0)));
+        grid.add_weighted(&shift((10, 0), (12, 0)), 0.5);
+        grid.add(&shift((22, 0), (2, 0)));
+        grid
+    }
+
+    #[rstest]
+    #[case(CoverageMode::Headcount, 9, 1.0)]
+    #[case(CoverageMode::Headcount, 11, 1.5)]
+    #[case(CoverageMode::Headcount, 12, 2.0)]
+    #[case(CoverageMode::Weighted, 12, 1.5)]
+    #[case(CoverageMode::Weighted, 19, 1.0)]
+    #[case(CoverageMode::Weighted, 20, 0.0)]
+    #[case(CoverageMode::Weighted, 23, 1.0)]
+    fn test_add(#[case] mode: CoverageMode, #[case] index: usize, #[case] expected: f64) {
+        let grid = hourly_grid(mode);
+        assert_eq!(grid.len(), 24);
+        assert_eq!(grid.value_at(index), Some(expected));
+    }
+
+    #[test]
+    fn test_add_shift_checks_period_length() {
+        let mut grid = CoverageGrid::for_date(date(20), PeriodLength::FIFTEEN_MINUTES, CoverageMode::Headcount);
+        let on_grid = DateTimeRangeWithPeriodLength::of(shift((9, 0), (10, 0)), PeriodLength::FIFTEEN_MINUTES);
+        let off_grid = DateTimeRangeWithPeriodLength::of(shift((9, 0), (10, 0)), PeriodLength::THIRTY_MINUTES);
+
+        assert_eq!(grid.add_shift(&on_grid), Ok(()));
+        assert_eq!(grid.add_shift(&off_grid), Err(CoverageGridError::PeriodLengthMismatch));
+        assert_eq!(grid.values().iter().sum::<f64>(), 4.0);
+    }
+
+    #[test]
+    fn test_add_shift_rejects_zoned_shift() {
+        let mut grid = CoverageGrid::for_date(date(20), PeriodLength::FIFTEEN_MINUTES, CoverageMode::Headcount);
+        let zoned = DateTimeRangeWithPeriodLength::of(shift((9, 0), (10, 0)), PeriodLength::FIFTEEN_MINUTES)
+            .with_zone(&Zone::utc(), LocalTimePolicy::default())
+            .unwrap();
+
+        assert_eq!(grid.add_shift(&zoned), Err(CoverageGridError::ZonedShift));
+        assert_eq!(grid.values().iter().sum::<f64>(), 0.0);
+    }
+
+    #[test]
+    fn test_multi_day_grid() {
+        let mut grid = CoverageGrid::for_date_range(&DateRange::new(date(20), date(21)), PeriodLength::SIXTY_MINUTES, CoverageMode::Headcount);
+        grid.add(&shift((22, 0), (2, 0)));
+
+        assert_eq!(grid.len(), 48);
+        assert_eq!(&grid.values()[22..26], &[1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(grid.period_range(25), DateTimeRange::of(date(21).and_hms_opt(1, 0, 0).unwrap(), date(21).and_hms_opt(2, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_staffing_intervals() {
+        let grid = hourly_grid(CoverageMode::Weighted);
+        let mut required = vec![0.0; 24];
+        required[9..20].fill(1.0);
+        required[14] = 3.0;
+        let required = CoverageGrid::from_values(date(20), PeriodLength::SIXTY_MINUTES, required);
+
+        let intervals = grid.staffing_intervals(&required).unwrap();
+        let summary: Vec<(StaffingStatus, (usize, usize), f64)> =
+            intervals.iter().map(|i| (i.status(), i.index_range(), i.peak_difference())).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (StaffingStatus::Over, (10, 13), 1.0),
+                (StaffingStatus::Under, (14, 14), -1.0),
+                (StaffingStatus::Over, (15, 16), 1.0),
+                (StaffingStatus::Over, (22, 23), 1.0),
+            ]
+        );
+        assert_eq!(intervals[0].range(), &shift((10, 0), (14, 0)));
+    }
+
+    #[test]
+    fn test_subtract_checks_shape() {
+        let grid = hourly_grid(CoverageMode::Headcount);
+        let other_day = CoverageGrid::for_date(date(21), PeriodLength::SIXTY_MINUTES, CoverageMode::Headcount);
+        let other_length = CoverageGrid::for_date(date(20), PeriodLength::THIRTY_MINUTES, CoverageMode::Headcount);
+
+        assert_eq!(grid.subtract(&other_day), Err(CoverageGridError::ShapeMismatch));
+        assert_eq!(grid.subtract(&other_length), Err(CoverageGridError::PeriodLengthMismatch));
+        assert_eq!(grid.subtract(&grid).unwrap().values(), &[0.0; 24]);
+    }
+}
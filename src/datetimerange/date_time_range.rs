@@ -1,3 +1,5 @@
+use crate::datetimerange::{BoundaryRule, DailyTimeBoundaries};
+use crate::dateutils::business_date_of;
 use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
@@ -82,6 +84,47 @@ impl DateTimeRange {
     pub fn contains_exclusive(&self, dt: NaiveDateTime) -> bool {
         dt > self.start && dt < self.end
     }
+
+    /// Split the range at midnight, pairing each segment with the date it falls on.
+    pub fn split_at_midnight(&self) -> Vec<(NaiveDate, DateTimeRange)> {
+        self.split_by_day(NaiveTime::MIN)
+    }
+
+    /// Split the range into segments for each business date, where business days
+    /// start at `day_start` (e.g. 04:00 for restaurants). Each segment is paired with
+    /// the business date it belongs to.
+    pub fn split_by_day(&self, day_start: NaiveTime) -> Vec<(NaiveDate, DateTimeRange)> {
+        self.split_by(&DailyTimeBoundaries::of(vec![day_start]))
+            .into_iter()
+            .map(|segment| (business_date_of(segment.start, day_start), segment))
+            .collect()
+    }
+
+    /// Split the range at each of the specified times. Times outside the range, or on
+    /// its start or end, are ignored. The segments are returned in order.
+    pub fn split_at_times(&self, times: &[NaiveDateTime]) -> Vec<DateTimeRange> {
+        let mut boundaries: Vec<NaiveDateTime> = times
+            .iter()
+            .copied()
+            .filter(|time| self.contains_exclusive(*time))
+            .collect();
+        boundaries.sort();
+        boundaries.dedup();
+
+        let mut segments = Vec::with_capacity(boundaries.len() + 1);
+        let mut current = self.start;
+        for boundary in boundaries {
+            segments.push(Self::of(current, boundary));
+            current = boundary;
+        }
+        segments.push(Self::of(current, self.end));
+        segments
+    }
+
+    /// Split the range at the boundaries produced by a `BoundaryRule`.
+    pub fn split_by(&self, rule: &dyn BoundaryRule) -> Vec<DateTimeRange> {
+        self.split_at_times(&rule.boundaries(self))
+    }
 }
 
 impl PartialEq for DateTimeRange {
@@ -113,3 +156,132 @@ impl Ord for DateTimeRange {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daterange::WeeklyDateRange;
+    use crate::datetimerange::{MinuteIntervalBoundaries, PeriodEndBoundaries};
+    use rstest::rstest;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[rstest]
+    #[case(time(9, 0), time(17, 0), 0, vec![(20, time(9, 0), 20, time(17, 0))])]
+    #[case(time(22, 0), time(6, 0), 0, vec![(20, time(22, 0), 21, time(0, 0)), (21, time(0, 0), 21, time(6, 0))])]
+    #[case(time(22, 0), time(6, 0), 4, vec![(20, time(22, 0), 21, time(4, 0)), (21, time(4, 0), 21, time(6, 0))])]
+    #[case(time(18, 0), time(3, 30), 4, vec![(20, time(18, 0), 21, time(3, 30))])]
+    #[case(time(2, 0), time(5, 0), 4, vec![(19, time(2, 0), 20, time(4, 0)), (20, time(4, 0), 20, time(5, 0))])]
+    fn test_split_by_day(
+        #[case] start_time: NaiveTime,
+        #[case] end_time: NaiveTime,
+        #[case] day_start_hour: u32,
+        #[case] expected: Vec<(u32, NaiveTime, u32, NaiveTime)>,
+    ) {
+        let range = DateTimeRange::from_time_range_on_date(start_time, end_time, date(20));
+        let segments = range.split_by_day(time(day_start_hour, 0));
+
+        let expected: Vec<(NaiveDate, DateTimeRange)> = expected
+            .into_iter()
+            .map(|(business_day, start, end_day, end)| {
+                let start_day = if start < time(day_start_hour, 0) { business_day + 1 } else { business_day };
+                (date(business_day), DateTimeRange::of(date(start_day).and_time(start), date(end_day).and_time(end)))
+            })
+            .collect();
+        assert_eq!(segments, expected);
+    }
+
+    #[test]
+    fn test_split_at_midnight_multi_day() {
+        let range = DateTimeRange::of(date(20).and_time(time(12, 0)), date(23).and_time(time(0, 0)));
+        let segments = range.split_at_midnight();
+
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[0], (date(20), DateTimeRange::of(date(20).and_time(time(12, 0)), date(21).and_time(time(0, 0)))));
+        assert_eq!(segments[2], (date(22), DateTimeRange::all_day(date(22))));
+    }
+
+    #[test]
+    fn test_split_at_times() {
+        let range = DateTimeRange::of(date(20).and_time(time(9, 0)), date(20).and_time(time(17, 0)));
+        let segments = range.split_at_times(&[
+            date(20).and_time(time(12, 0)),
+            date(20).and_time(time(9, 0)),
+            date(20).and_time(time(10, 30)),
+            date(20).and_time(time(12, 0)),
+            date(20).and_time(time(18, 0)),
+        ]);
+
+        assert_eq!(
+            segments,
+            vec![
+                DateTimeRange::of(date(20).and_time(time(9, 0)), date(20).and_time(time(10, 30))),
+                DateTimeRange::of(date(20).and_time(time(10, 30)), date(20).and_time(time(12, 0))),
+                DateTimeRange::of(date(20).and_time(time(12, 0)), date(20).and_time(time(17, 0))),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(time(9, 10), time(12, 0), 60, vec![time(9, 10), time(10, 0), time(11, 0), time(12, 0)])]
+    #[case(time(9, 0), time(10, 0), 15, vec![time(9, 0), time(9, 15), time(9, 30), time(9, 45), time(10, 0)])]
+    #[case(time(9, 0), time(9, 0), 15, vec![time(9, 0), time(9, 0)])]
+    fn test_split_by_minute_interval(
+        #[case] start: NaiveTime,
+        #[case] end: NaiveTime,
+        #[case] minutes: u32,
+        #[case] expected_edges: Vec<NaiveTime>,
+    ) {
+        let range = DateTimeRange::from_time_range_on_date(start, end, date(20));
+        let segments = range.split_by(&MinuteIntervalBoundaries::of(minutes).unwrap());
+
+        let expected: Vec<DateTimeRange> = expected_edges
+            .windows(2)
+            .map(|w| DateTimeRange::of(date(20).and_time(w[0]), date(20).and_time(w[1])))
+            .collect();
+        assert_eq!(segments, expected);
+    }
+
+    #[test]
+    fn test_minute_interval_of_zero() {
+        assert_eq!(MinuteIntervalBoundaries::of(0), None);
+        assert_eq!(MinuteIntervalBoundaries::of(60), Some(MinuteIntervalBoundaries::hourly()));
+    }
+
+    #[test]
+    fn test_split_by_daily_times() {
+        let range = DateTimeRange::from_time_range_on_date(time(18, 0), time(7, 0), date(20));
+        let segments = range.split_by(&DailyTimeBoundaries::of(vec![time(22, 0), time(6, 0)]));
+
+        assert_eq!(
+            segments,
+            vec![
+                DateTimeRange::of(date(20).and_time(time(18, 0)), date(20).and_time(time(22, 0))),
+                DateTimeRange::of(date(20).and_time(time(22, 0)), date(21).and_time(time(6, 0))),
+                DateTimeRange::of(date(21).and_time(time(6, 0)), date(21).and_time(time(7, 0))),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_by_period_ends() {
+        let pay_period = WeeklyDateRange::with_end_date(date(23));
+        let range = DateTimeRange::of(date(23).and_time(time(20, 0)), date(31).and_time(time(4, 0)));
+        let segments = range.split_by(&PeriodEndBoundaries::of(pay_period));
+
+        assert_eq!(
+            segments,
+            vec![
+                DateTimeRange::of(date(23).and_time(time(20, 0)), date(24).and_time(time(0, 0))),
+                DateTimeRange::of(date(24).and_time(time(0, 0)), date(31).and_time(time(0, 0))),
+                DateTimeRange::of(date(31).and_time(time(0, 0)), date(31).and_time(time(4, 0))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use crate::datetimerange::DateTimeRange;
+use crate::timerange::{ends_next_day, parse_times, TimeRange, TimeRangeFormat, TimeRangeParseError};
+use chrono::{Days, NaiveDate};
+use std::fmt;
+use std::str::FromStr;
+
+/// Parses a start date followed by a time range, e.g. `2025-08-18 21:00-05:30 (+1)`. Without
+/// a day marker the range ends on the next day when its end time is not after its start.
+impl FromStr for DateTimeRange {
+    type Err = TimeRangeParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(TimeRangeParseError::Empty);
+        }
+
+        let (date_text, times) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let date = NaiveDate::parse_from_str(date_text, "%Y-%m-%d")
+            .map_err(|_| TimeRangeParseError::InvalidDate(date_text.to_string()))?;
+
+        let (start, end, days) = parse_times(times)?;
+        let days = days.unwrap_or_else(|| {
+            if ends_next_day(&TimeRange::of(start, end)) {
+                1
+            } else {
+                0
+            }
+        });
+        let end_date = date
+            .checked_add_days(Days::new(days.into()))
+            .ok_or_else(|| TimeRangeParseError::InvalidDayMarker(format!("(+{})", days)))?;
+        Ok(DateTimeRange::of(date.and_time(start), end_date.and_time(end)))
+    }
+}
+
+impl fmt::Display for DateTimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", TimeRangeFormat::default().format_date_time_range(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use rstest::rstest;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case("2025-08-18 09:00-17:00", DateTimeRange::of(at(18, 9, 0), at(18, 17, 0)))]
+    #[case("2025-08-18 21:00-05:30", DateTimeRange::of(at(18, 21, 0), at(19, 5, 30)))]
+    #[case("2025-08-18 21:00-05:30 (+1)", DateTimeRange::of(at(18, 21, 0), at(19, 5, 30)))]
+    #[case("2025-08-18 9a-5p (+1)", DateTimeRange::of(at(18, 9, 0), at(19, 17, 0)))]
+    #[case("2025-08-18 21:00-05:30 (+2)", DateTimeRange::of(at(18, 21, 0), at(20, 5, 30)))]
+    fn test_parse_date_time_range(#[case] text: &str, #[case] expected: DateTimeRange) {
+        assert_eq!(text.parse::<DateTimeRange>(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("2025-13-01 09:00-17:00", TimeRangeParseError::InvalidDate("2025-13-01".to_string()))]
+    #[case("2025-08-18", TimeRangeParseError::Empty)]
+    #[case(
+        "2025-08-18 09:00-17:00 (+4000000000)",
+        TimeRangeParseError::InvalidDayMarker("(+4000000000)".to_string())
+    )]
+    fn test_parse_date_time_range_invalid(#[case] text: &str, #[case] expected: TimeRangeParseError) {
+        assert_eq!(text.parse::<DateTimeRange>(), Err(expected));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            DateTimeRange::of(at(18, 9, 0), at(18, 17, 0)).to_string(),
+            "2025-08-18 09:00-17:00"
+        );
+    }
+}
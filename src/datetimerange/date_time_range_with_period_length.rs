@@ -1,49 +1,202 @@
-use crate::datetimerange::DateTimeRange;
-use chrono::{Duration, NaiveDateTime, Timelike};
+use crate::datetimerange::{DateTimeRange, PeriodLength, ZonedDateTimeRange};
+use crate::timezone::{LocalTimePolicy, Zone, ZoneError};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use std::hash::{Hash, Hasher};
 use std::iter::Iterator;
 
-const MINUTES_PER_HOUR: i32 = 60;
-const MINUTES_PER_DAY: i32 = 1440;
-
+/// A `DateTimeRange` on a grid of fixed-length periods starting at midnight.
+///
+/// By default periods are indexed by wall-clock time. With a zone set by `with_zone`,
+/// periods are indexed by elapsed time from the start of the local day instead, so on a
+/// day with a daylight saving time transition the indexes stay consistent with
+/// `number_of_periods_in_shift`. On a 23-hour day the skipped hour has no periods, and
+/// on a 25-hour day the repeated hour gets its own periods after the first occurrence.
 #[derive(Debug, Clone)]
 pub struct DateTimeRangeWithPeriodLength {
     date_time_range: DateTimeRange,
-    period_length_minutes: i32,
+    period_length: PeriodLength,
+    zoned: Option<ZonedDateTimeRange>,
 }
 
 impl DateTimeRangeWithPeriodLength {
-    pub fn of(date_time_range: DateTimeRange, period_length_minutes: i32) -> Self {
+    pub fn of(date_time_range: DateTimeRange, period_length: PeriodLength) -> Self {
         Self {
             date_time_range,
-            period_length_minutes,
+            period_length,
+            zoned: None,
         }
     }
 
-    pub fn of_datetimes(start: NaiveDateTime, end: NaiveDateTime, period_length_minutes: i32) -> Self {
-        Self::of(DateTimeRange::of(start, end), period_length_minutes)
+    pub fn of_datetimes(start: NaiveDateTime, end: NaiveDateTime, period_length: PeriodLength) -> Self {
+        Self::of(DateTimeRange::of(start, end), period_length)
+    }
+
+    /// Index periods by elapsed time in the zone, resolving the range's wall-clock
+    /// start and end with the policy.
+    pub fn with_zone(self, zone: &Zone, policy: LocalTimePolicy) -> Result<Self, ZoneError> {
+        let zoned = ZonedDateTimeRange::from_local(&self.date_time_range, zone, policy)?;
+        Ok(Self {
+            zoned: Some(zoned),
+            ..self
+        })
+    }
+
+    /// Get the zone the periods are indexed in, if any.
+    pub fn zone(&self) -> Option<&Zone> {
+        self.zoned.as_ref().map(|zoned| zoned.zone())
     }
 
+    /// Get the index of the period containing the start, relative to midnight of the start date.
     pub fn start_index(&self) -> i32 {
-        let start = self.date_time_range.start();
-        (start.hour() as i32 * MINUTES_PER_HOUR + start.minute() as i32) / self.period_length_minutes
+        self.periods_in(self.offsets().0)
     }
 
+    /// Get the index of the period containing the end, relative to midnight of the start date.
+    /// Ranges that end on a later date have indexes past the end of the first day.
     pub fn end_index(&self) -> i32 {
-        let start = self.date_time_range.start();
-        let end = self.date_time_range.end();
+        self.periods_in(self.offsets().1)
+    }
 
-        let mut end_index = end.hour() as i32 * MINUTES_PER_HOUR + end.minute() as i32;
+    /// Get an iterator over the periods the range touches, yielding each period's index
+    /// (relative to midnight of the start date) and the fraction of it that is covered.
+    ///
+    /// A shift from 09:10 to 17:05 on a 15-minute grid yields `(36, 0.333..)` for the
+    /// 09:00 period, `1.0` for the periods in between, and `(68, 0.333..)` for 17:00.
+    pub fn coverage(&self) -> CoverageIterator {
+        let (start, end) = self.offsets();
+        let length = self.period_length.as_duration().num_milliseconds();
+        let start = start.num_milliseconds();
+        let end = end.num_milliseconds().max(start);
+        let current = start.div_euclid(length);
 
-        if end.date() > start.date() {
-            end_index += MINUTES_PER_DAY;
+        CoverageIterator {
+            current,
+            end_exclusive: if end > start { (end + length - 1).div_euclid(length) } else { current },
+            start,
+            end,
+            length,
         }
+    }
 
-        end_index / self.period_length_minutes
+    /// Get an iterator over the periods in the range, each as a `DateTimeRange` paired with
+    /// the index of the period its start falls in. Periods are stepped from the start of
+    /// the range, and the last one is cut short at the end of the range.
+    ///
+    /// With a zone, periods are stepped in elapsed time and returned as UTC date-times, so a
+    /// period in the repeated hour of a fold still ends after it starts. Use
+    /// `ZonedDateTimeRange::from_utc` to get a period in the zone.
+    pub fn periods(&self) -> PeriodIterator {
+        self.period_iterator(false)
     }
 
-    pub fn period_length_in_minutes(&self) -> i32 {
-        self.period_length_minutes
+    /// Get an iterator over the periods in the range, aligned to the period grid. When the
+    /// range starts or ends mid-period, the first or last period is cut short at the range
+    /// boundary, so a shift from 09:10 on a 15-minute grid yields 09:10-09:15, 09:15-09:30, ...
+    /// With a zone, periods are returned as UTC date-times as described for `periods`.
+    pub fn aligned_periods(&self) -> PeriodIterator {
+        self.period_iterator(true)
+    }
+
+    fn period_iterator(&self, aligned: bool) -> PeriodIterator {
+        let (start, end) = self.offsets();
+        let length = self.period_length.as_duration().num_milliseconds();
+        let start = start.num_milliseconds();
+        let end = end.num_milliseconds().max(start);
+        let start_date = self.date_time_range.start().date();
+
+        let origin = match &self.zoned {
+            Some(zoned) => zoned.zone().start_of_day(start_date).naive_utc(),
+            None => start_date.and_time(NaiveTime::MIN),
+        };
+        let count = if end == start {
+            0
+        } else if aligned {
+            (end + length - 1).div_euclid(length) - start.div_euclid(length)
+        } else {
+            (end - start + length - 1).div_euclid(length)
+        };
+
+        PeriodIterator {
+            origin,
+            start,
+            end,
+            length,
+            aligned,
+            front: 0,
+            back: count,
+        }
+    }
+
+    /// Get the elapsed time from the start of the start date to the start and end of the range.
+    fn offsets(&self) -> (Duration, Duration) {
+        let start_date = self.date_time_range.start().date();
+        match &self.zoned {
+            Some(zoned) => {
+                let day_start = zoned.zone().start_of_day(start_date);
+                (zoned.start() - day_start, zoned.end() - day_start)
+            }
+            None => {
+                let day_start = start_date.and_time(NaiveTime::MIN);
+                (self.date_time_range.start() - day_start, self.date_time_range.end() - day_start)
+            }
+        }
+    }
+
+    /// Convert a period index on a date to an index relative to midnight of the start date.
+    pub fn absolute_index(&self, date: NaiveDate, period_index: i32) -> i32 {
+        let start_date = self.date_time_range.start().date();
+        if self.zoned.is_none() {
+            let days = (date - start_date).num_days() as i32;
+            return days * self.periods_on_date(date) + period_index;
+        }
+
+        let mut index = period_index;
+        let mut current = start_date;
+        while current < date {
+            index += self.periods_on_date(current);
+            current += Duration::days(1);
+        }
+        while current > date {
+            current -= Duration::days(1);
+            index -= self.periods_on_date(current);
+        }
+        index
+    }
+
+    /// Convert an index relative to midnight of the start date to the date it falls on
+    /// and the period index within that date.
+    pub fn date_and_period(&self, absolute_index: i32) -> (NaiveDate, i32) {
+        let start_date = self.date_time_range.start().date();
+        if self.zoned.is_none() {
+            let periods_per_day = self.periods_on_date(start_date);
+            let days = absolute_index.div_euclid(periods_per_day);
+            return (start_date + Duration::days(days as i64), absolute_index.rem_euclid(periods_per_day));
+        }
+
+        let mut date = start_date;
+        let mut index = absolute_index;
+        while index < 0 {
+            date -= Duration::days(1);
+            index += self.periods_on_date(date);
+        }
+        while index >= self.periods_on_date(date) {
+            index -= self.periods_on_date(date);
+            date += Duration::days(1);
+        }
+        (date, index)
+    }
+
+    pub fn period_length(&self) -> PeriodLength {
+        self.period_length
+    }
+
+    /// Get the period length in minutes, or `None` if it is not a whole number of minutes.
+    pub fn period_length_in_minutes(&self) -> Option<i32> {
+        if self.period_length.as_seconds() % 60 == 0 {
+            Some(self.period_length.as_minutes())
+        } else {
+            None
+        }
     }
 
     pub fn date_time_range(&self) -> &DateTimeRange {
@@ -55,7 +208,23 @@ impl DateTimeRangeWithPeriodLength {
     }
 
     pub fn number_of_periods_in_shift(&self) -> i32 {
-        (self.date_time_range.duration().num_minutes() as i32) / self.period_length_minutes
+        match &self.zoned {
+            Some(zoned) => self.periods_in(zoned.duration()),
+            None => self.periods_in(self.date_time_range.duration()),
+        }
+    }
+
+    /// Get the number of periods on the date, which is fewer or more than usual on
+    /// daylight saving time transition days when a zone is set.
+    pub fn periods_on_date(&self, date: NaiveDate) -> i32 {
+        match &self.zoned {
+            Some(zoned) => self.periods_in(zoned.zone().day_length(date)),
+            None => self.period_length.periods_per_day(),
+        }
+    }
+
+    fn periods_in(&self, duration: Duration) -> i32 {
+        self.period_length.periods_in(duration)
     }
 }
 
@@ -72,7 +241,105 @@ impl Hash for DateTimeRangeWithPeriodLength {
     }
 }
 
-/// Iterator over the `NaiveDateTime`s in the range
+/// Iterator over the periods covered by a `DateTimeRangeWithPeriodLength`, yielding
+/// `(period_index, fraction_covered)`.
+pub struct CoverageIterator {
+    current: i64,
+    end_exclusive: i64,
+    start: i64,
+    end: i64,
+    length: i64,
+}
+
+impl Iterator for CoverageIterator {
+    type Item = (i32, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current >= self.end_exclusive {
+            return None;
+        }
+
+        let period_start = self.current * self.length;
+        let covered = (period_start + self.length).min(self.end) - period_start.max(self.start);
+        let result = (self.current as i32, covered as f64 / self.length as f64);
+        self.current += 1;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end_exclusive - self.current).max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for CoverageIterator {}
+
+/// Iterator over the periods of a `DateTimeRangeWithPeriodLength`, yielding
+/// `(period_index, period)`. The end of the range is never the start of a period.
+#[derive(Debug, Clone)]
+pub struct PeriodIterator {
+    /// Midnight of the start date, in UTC when the periods are zoned.
+    origin: NaiveDateTime,
+    start: i64,
+    end: i64,
+    length: i64,
+    aligned: bool,
+    front: i64,
+    back: i64,
+}
+
+impl PeriodIterator {
+    fn period(&self, position: i64) -> (i32, DateTimeRange) {
+        let period_start = if self.aligned {
+            (self.start.div_euclid(self.length) + position) * self.length
+        } else {
+            self.start + position * self.length
+        };
+        let from = period_start.max(self.start);
+        let to = (period_start + self.length).min(self.end);
+        let index = from.div_euclid(self.length) as i32;
+
+        (
+            index,
+            DateTimeRange::of(self.origin + Duration::milliseconds(from), self.origin + Duration::milliseconds(to)),
+        )
+    }
+}
+
+impl Iterator for PeriodIterator {
+    type Item = (i32, DateTimeRange);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        let result = self.period(self.front);
+        self.front += 1;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.back - self.front).max(0) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for PeriodIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.period(self.back))
+    }
+}
+
+impl ExactSizeIterator for PeriodIterator {}
+
+/// Iterator over the `NaiveDateTime`s in the range, stepping by the period length.
+///
+/// The end of the range is included when it falls on a step; use
+/// `DateTimeRangeWithPeriodLength::periods` to iterate over whole periods instead.
 pub struct DateTimeRangeIterator {
     current: NaiveDateTime,
     end: NaiveDateTime,
@@ -80,11 +347,11 @@ pub struct DateTimeRangeIterator {
 }
 
 impl DateTimeRangeIterator {
-    fn new(range: &DateTimeRange, period_minutes: i32) -> Self {
+    fn new(range: &DateTimeRange, period_length: PeriodLength) -> Self {
         Self {
             current: range.start(),
             end: range.end(),
-            step: Duration::minutes(period_minutes as i64),
+            step: period_length.as_duration(),
         }
     }
 }
@@ -109,6 +376,280 @@ impl IntoIterator for DateTimeRangeWithPeriodLength {
     type IntoIter = DateTimeRangeIterator;
 
     fn into_iter(self) -> Self::IntoIter {
-        DateTimeRangeIterator::new(&self.date_time_range, self.period_length_minutes)
+        DateTimeRangeIterator::new(&self.date_time_range, self.period_length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn zone() -> Zone {
+        Zone::from_posix_tz("EST5EDT,M3.2.0,M11.1.0").unwrap()
+    }
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap().and_time(NaiveTime::from_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    #[rstest]
+    #[case(at(7, 1, 9, 0), at(7, 1, 17, 0), 36, 68, 32)]
+    #[case(at(7, 1, 22, 0), at(7, 2, 6, 0), 88, 120, 32)]
+    #[case(at(7, 1, 6, 0), at(7, 2, 18, 0), 24, 168, 144)]
+    #[case(at(7, 1, 9, 0), at(7, 4, 9, 0), 36, 324, 288)]
+    fn test_indexes(
+        #[case] start: NaiveDateTime,
+        #[case] end: NaiveDateTime,
+        #[case] expected_start_index: i32,
+        #[case] expected_end_index: i32,
+        #[case] expected_periods: i32,
+    ) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(start, end, PeriodLength::FIFTEEN_MINUTES);
+
+        assert_eq!(range.start_index(), expected_start_index);
+        assert_eq!(range.end_index(), expected_end_index);
+        assert_eq!(range.number_of_periods_in_shift(), expected_periods);
+        assert_eq!(range.index_range(), (expected_start_index, expected_end_index - 1));
+        assert_eq!(range.period_length_in_minutes(), Some(15));
+    }
+
+    #[test]
+    fn test_second_level_periods() {
+        let start = at(7, 1, 9, 0) + Duration::seconds(45);
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(start, at(7, 1, 9, 5), PeriodLength::seconds(30).unwrap());
+
+        assert_eq!(range.start_index(), 9 * 120 + 1);
+        assert_eq!(range.end_index(), 9 * 120 + 10);
+        assert_eq!(range.number_of_periods_in_shift(), 8);
+        assert_eq!(range.periods_on_date(start.date()), 2880);
+        assert_eq!(range.period_length_in_minutes(), None);
+    }
+
+    #[rstest]
+    #[case(at(7, 1, 9, 10), at(7, 1, 17, 5), 36, 69, 1.0 / 3.0, 1.0 / 3.0)]
+    #[case(at(7, 1, 9, 0), at(7, 1, 17, 0), 36, 68, 1.0, 1.0)]
+    #[case(at(7, 1, 9, 5), at(7, 1, 9, 10), 36, 37, 1.0 / 3.0, 1.0 / 3.0)]
+    #[case(at(7, 1, 23, 50), at(7, 2, 0, 20), 95, 98, 2.0 / 3.0, 1.0 / 3.0)]
+    fn test_coverage(
+        #[case] start: NaiveDateTime,
+        #[case] end: NaiveDateTime,
+        #[case] expected_first_index: i32,
+        #[case] expected_end_index: i32,
+        #[case] expected_first_fraction: f64,
+        #[case] expected_last_fraction: f64,
+    ) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(start, end, PeriodLength::FIFTEEN_MINUTES);
+        let coverage: Vec<(i32, f64)> = range.coverage().collect();
+
+        assert_eq!(coverage.len(), (expected_end_index - expected_first_index) as usize);
+        assert_eq!(range.coverage().len(), coverage.len());
+        assert_eq!(coverage.first().unwrap().0, expected_first_index);
+        assert_eq!(coverage.last().unwrap().0, expected_end_index - 1);
+        assert!((coverage.first().unwrap().1 - expected_first_fraction).abs() < 1e-9);
+        assert!((coverage.last().unwrap().1 - expected_last_fraction).abs() < 1e-9);
+        if coverage.len() > 2 {
+            assert!(coverage[1..coverage.len() - 1].iter().all(|(_, fraction)| *fraction == 1.0));
+        }
+
+        let total: f64 = coverage.iter().map(|(_, fraction)| fraction).sum();
+        let expected_total = range.date_time_range().duration().num_seconds() as f64 / 900.0;
+        assert!((total - expected_total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_coverage_empty_range() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(7, 1, 9, 10), at(7, 1, 9, 10), PeriodLength::FIFTEEN_MINUTES);
+        assert_eq!(range.coverage().count(), 0);
+    }
+
+    #[test]
+    fn test_zoned_coverage() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(3, 9, 1, 50), at(3, 9, 3, 10), PeriodLength::FIFTEEN_MINUTES)
+            .with_zone(&zone(), LocalTimePolicy::default())
+            .unwrap();
+        let coverage: Vec<(i32, f64)> = range.coverage().collect();
+
+        // 01:50 EST to 03:10 EDT is 20 minutes of elapsed time
+        assert_eq!(coverage.iter().map(|(index, _)| *index).collect::<Vec<i32>>(), vec![7, 8]);
+        assert!((coverage[0].1 - 2.0 / 3.0).abs() < 1e-9);
+        assert!((coverage[1].1 - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    fn ranges(periods: impl Iterator<Item = (i32, DateTimeRange)>) -> Vec<String> {
+        periods
+            .map(|(index, period)| format!("{} {}-{}", index, period.start().format("%H:%M"), period.end().format("%H:%M")))
+            .collect()
+    }
+
+    fn zoned_ranges(periods: impl Iterator<Item = (i32, DateTimeRange)>) -> Vec<String> {
+        periods
+            .map(|(index, period)| {
+                let period = ZonedDateTimeRange::from_utc(&period, &zone());
+                format!("{} {}-{}", index, period.start().format("%H:%M%:z"), period.end().format("%H:%M%:z"))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_periods() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(7, 1, 9, 10), at(7, 1, 9, 50), PeriodLength::FIFTEEN_MINUTES);
+
+        assert_eq!(
+            ranges(range.periods()),
+            vec!["36 09:10-09:25", "37 09:25-09:40", "38 09:40-09:50"]
+        );
+        assert_eq!(
+            ranges(range.aligned_periods()),
+            vec!["36 09:10-09:15", "37 09:15-09:30", "38 09:30-09:45", "39 09:45-09:50"]
+        );
+        assert_eq!(
+            ranges(range.aligned_periods().rev()),
+            vec!["39 09:45-09:50", "38 09:30-09:45", "37 09:15-09:30", "36 09:10-09:15"]
+        );
+        assert_eq!(range.periods().len(), 3);
+        assert_eq!(range.aligned_periods().len(), 4);
+    }
+
+    #[test]
+    fn test_periods_exclude_end() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(7, 1, 23, 0), at(7, 2, 1, 0), PeriodLength::SIXTY_MINUTES);
+
+        assert_eq!(range.periods().collect::<Vec<_>>(), range.aligned_periods().collect::<Vec<_>>());
+        assert_eq!(ranges(range.periods()), vec!["23 23:00-00:00", "24 00:00-01:00"]);
+        assert_eq!(range.clone().into_iter().count(), 3);
+
+        let mut periods = range.periods();
+        assert_eq!(periods.next_back().map(|(index, _)| index), Some(24));
+        assert_eq!(periods.next().map(|(index, _)| index), Some(23));
+        assert_eq!(periods.next(), None);
+        assert_eq!(periods.next_back(), None);
+    }
+
+    #[test]
+    fn test_zoned_periods() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(11, 2, 0, 30), at(11, 2, 2, 30), PeriodLength::SIXTY_MINUTES)
+            .with_zone(&zone(), LocalTimePolicy::default())
+            .unwrap();
+
+        // 01:00 to 02:00 happens twice, so the shift is three hours long. Period 1 runs from
+        // 01:00 EDT to 01:00 EST, which is a full hour.
+        assert_eq!(
+            ranges(range.aligned_periods()),
+            vec!["0 04:30-05:00", "1 05:00-06:00", "2 06:00-07:00", "3 07:00-07:30"]
+        );
+        assert_eq!(
+            zoned_ranges(range.aligned_periods()),
+            vec![
+                "0 00:30-04:00-01:00-04:00",
+                "1 01:00-04:00-01:00-05:00",
+                "2 01:00-05:00-02:00-05:00",
+                "3 02:00-05:00-02:30-05:00"
+            ]
+        );
+        assert_eq!(range.aligned_periods().len() as i32, range.end_index() - range.start_index() + 1);
+    }
+
+    #[test]
+    fn test_zoned_periods_in_fold() {
+        let range =
+            DateTimeRangeWithPeriodLength::of_datetimes(at(11, 2, 1, 30), at(11, 2, 2, 0), PeriodLength::FIFTEEN_MINUTES)
+                .with_zone(&zone(), LocalTimePolicy::default())
+                .unwrap();
+
+        // From 01:30 EDT through the repeated hour to 02:00 EST. The period from 01:45 EDT
+        // to 01:00 EST would end before it starts on the wall clock.
+        assert_eq!(
+            zoned_ranges(range.periods()),
+            vec![
+                "6 01:30-04:00-01:45-04:00",
+                "7 01:45-04:00-01:00-05:00",
+                "8 01:00-05:00-01:15-05:00",
+                "9 01:15-05:00-01:30-05:00",
+                "10 01:30-05:00-01:45-05:00",
+                "11 01:45-05:00-02:00-05:00"
+            ]
+        );
+        assert!(range.periods().all(|(_, period)| period.start() < period.end()));
+        assert_eq!(range.number_of_periods_in_shift(), 6);
+    }
+
+    #[test]
+    fn test_empty_periods() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(7, 1, 9, 10), at(7, 1, 9, 10), PeriodLength::FIFTEEN_MINUTES);
+        assert_eq!(range.periods().count(), 0);
+        assert_eq!(range.aligned_periods().count(), 0);
+    }
+
+    #[rstest]
+    #[case(at(7, 1, 0, 0).date(), 0, 0)]
+    #[case(at(7, 1, 0, 0).date(), 95, 95)]
+    #[case(at(7, 2, 0, 0).date(), 0, 96)]
+    #[case(at(7, 3, 0, 0).date(), 10, 202)]
+    #[case(at(6, 30, 0, 0).date(), 95, -1)]
+    fn test_absolute_index(#[case] date: NaiveDate, #[case] period_index: i32, #[case] expected: i32) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(7, 1, 9, 0), at(7, 3, 17, 0), PeriodLength::FIFTEEN_MINUTES);
+
+        assert_eq!(range.absolute_index(date, period_index), expected);
+        assert_eq!(range.date_and_period(expected), (date, period_index));
+    }
+
+    #[rstest]
+    #[case(at(3, 6, 0, 0).date(), 95, -1)]
+    #[case(at(3, 8, 0, 0).date(), 10, 106)]
+    #[case(at(3, 9, 0, 0).date(), 91, 283)]
+    #[case(at(3, 10, 0, 0).date(), 0, 284)]
+    fn test_zoned_absolute_index(#[case] date: NaiveDate, #[case] period_index: i32, #[case] expected: i32) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(3, 7, 9, 0), at(3, 7, 17, 0), PeriodLength::FIFTEEN_MINUTES)
+            .with_zone(&zone(), LocalTimePolicy::default())
+            .unwrap();
+
+        assert_eq!(range.absolute_index(date, period_index), expected);
+        assert_eq!(range.date_and_period(expected), (date, period_index));
+    }
+
+    #[rstest]
+    #[case(at(7, 1, 9, 0), at(7, 1, 17, 0), 36, 68, 32)]
+    #[case(at(3, 9, 1, 0), at(3, 9, 4, 0), 4, 12, 8)]
+    #[case(at(3, 9, 0, 0), at(3, 10, 0, 0), 0, 92, 92)]
+    #[case(at(11, 2, 0, 0), at(11, 3, 0, 0), 0, 100, 100)]
+    #[case(at(11, 2, 3, 0), at(11, 2, 5, 0), 16, 24, 8)]
+    #[case(at(11, 1, 22, 0), at(11, 2, 6, 0), 88, 124, 36)]
+    fn test_zoned_indexes(
+        #[case] start: NaiveDateTime,
+        #[case] end: NaiveDateTime,
+        #[case] expected_start_index: i32,
+        #[case] expected_end_index: i32,
+        #[case] expected_periods: i32,
+    ) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(start, end, PeriodLength::FIFTEEN_MINUTES)
+            .with_zone(&zone(), LocalTimePolicy::default())
+            .unwrap();
+
+        assert_eq!(range.start_index(), expected_start_index);
+        assert_eq!(range.end_index(), expected_end_index);
+        assert_eq!(range.number_of_periods_in_shift(), expected_periods);
+        assert_eq!(range.end_index() - range.start_index(), range.number_of_periods_in_shift());
+    }
+
+    #[rstest]
+    #[case(at(3, 8, 0, 0).date(), 96)]
+    #[case(at(3, 9, 0, 0).date(), 92)]
+    #[case(at(11, 2, 0, 0).date(), 100)]
+    fn test_periods_on_date(#[case] date: NaiveDate, #[case] expected: i32) {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(3, 9, 0, 0), at(3, 9, 1, 0), PeriodLength::FIFTEEN_MINUTES);
+        assert_eq!(range.periods_on_date(date), 96);
+
+        let range = range.with_zone(&zone(), LocalTimePolicy::default()).unwrap();
+        assert_eq!(range.periods_on_date(date), expected);
+    }
+
+    #[test]
+    fn test_with_zone_rejects_ambiguous_time_with_strict_policy() {
+        let range = DateTimeRangeWithPeriodLength::of_datetimes(at(11, 2, 1, 30), at(11, 2, 5, 0), PeriodLength::FIFTEEN_MINUTES);
+        assert_eq!(
+            range.with_zone(&zone(), LocalTimePolicy::strict()).unwrap_err(),
+            ZoneError::AmbiguousLocalTime(at(11, 2, 1, 30))
+        );
     }
 }
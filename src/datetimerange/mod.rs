@@ -1,5 +1,22 @@
 pub mod date_time_range;
 pub use date_time_range::*;
 
+pub mod date_time_range_format;
+
 pub mod date_time_range_with_period_length;
 pub use date_time_range_with_period_length::*;
+
+pub mod period_length;
+pub use period_length::*;
+
+pub mod boundary_rule;
+pub use boundary_rule::*;
+
+pub mod zoned_date_time_range;
+pub use zoned_date_time_range::*;
+
+pub mod coverage_grid;
+pub use coverage_grid::*;
+
+pub mod punch_rounding;
+pub use punch_rounding::*;
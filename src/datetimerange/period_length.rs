@@ -0,0 +1,134 @@
+use chrono::Duration;
+use std::error::Error;
+use std::fmt;
+
+const SECONDS_PER_MINUTE: i32 = 60;
+const SECONDS_PER_DAY: i32 = 86_400;
+
+/// The length of the periods in a grid over a day.
+///
+/// A period length is always positive and divides a day exactly, so periods line up
+/// with midnight on every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PeriodLength {
+    seconds: i32,
+}
+
+/// Errors from creating a `PeriodLength`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeriodLengthError {
+    /// The length, in seconds, is zero or negative.
+    NotPositive(i64),
+    /// The length, in seconds, does not divide a day.
+    DoesNotDivideDay(i64),
+}
+
+impl fmt::Display for PeriodLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PeriodLengthError::NotPositive(seconds) => {
+                write!(f, "period length of {} seconds is not positive", seconds)
+            }
+            PeriodLengthError::DoesNotDivideDay(seconds) => {
+                write!(f, "period length of {} seconds does not divide a day", seconds)
+            }
+        }
+    }
+}
+
+impl Error for PeriodLengthError {}
+
+impl PeriodLength {
+    pub const FIVE_MINUTES: PeriodLength = PeriodLength { seconds: 5 * SECONDS_PER_MINUTE };
+    pub const FIFTEEN_MINUTES: PeriodLength = PeriodLength { seconds: 15 * SECONDS_PER_MINUTE };
+    pub const THIRTY_MINUTES: PeriodLength = PeriodLength { seconds: 30 * SECONDS_PER_MINUTE };
+    pub const SIXTY_MINUTES: PeriodLength = PeriodLength { seconds: 60 * SECONDS_PER_MINUTE };
+
+    /// Create a period length in minutes.
+    pub fn minutes(minutes: i32) -> Result<Self, PeriodLengthError> {
+        Self::seconds_i64(minutes as i64 * SECONDS_PER_MINUTE as i64)
+    }
+
+    /// Create a period length in seconds.
+    pub fn seconds(seconds: i32) -> Result<Self, PeriodLengthError> {
+        Self::seconds_i64(seconds as i64)
+    }
+
+    fn seconds_i64(seconds: i64) -> Result<Self, PeriodLengthError> {
+        if seconds <= 0 {
+            Err(PeriodLengthError::NotPositive(seconds))
+        } else if SECONDS_PER_DAY as i64 % seconds != 0 {
+            Err(PeriodLengthError::DoesNotDivideDay(seconds))
+        } else {
+            Ok(Self { seconds: seconds as i32 })
+        }
+    }
+
+    /// Get the length in whole seconds.
+    pub fn as_seconds(&self) -> i32 {
+        self.seconds
+    }
+
+    /// Get the length in whole minutes, truncating any partial minute.
+    pub fn as_minutes(&self) -> i32 {
+        self.seconds / SECONDS_PER_MINUTE
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        Duration::seconds(self.seconds as i64)
+    }
+
+    /// Get the number of periods in a 24-hour day.
+    pub fn periods_per_day(&self) -> i32 {
+        SECONDS_PER_DAY / self.seconds
+    }
+
+    /// Get the number of whole periods in the duration.
+    pub fn periods_in(&self, duration: Duration) -> i32 {
+        (duration.num_seconds() / self.seconds as i64) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 1440)]
+    #[case(5, 288)]
+    #[case(15, 96)]
+    #[case(30, 48)]
+    #[case(60, 24)]
+    #[case(1440, 1)]
+    fn test_minutes(#[case] minutes: i32, #[case] expected_periods: i32) {
+        let length = PeriodLength::minutes(minutes).unwrap();
+        assert_eq!(length.as_minutes(), minutes);
+        assert_eq!(length.periods_per_day(), expected_periods);
+    }
+
+    #[rstest]
+    #[case(0, PeriodLengthError::NotPositive(0))]
+    #[case(-15, PeriodLengthError::NotPositive(-900))]
+    #[case(7, PeriodLengthError::DoesNotDivideDay(420))]
+    #[case(2880, PeriodLengthError::DoesNotDivideDay(172_800))]
+    fn test_invalid_minutes(#[case] minutes: i32, #[case] expected: PeriodLengthError) {
+        assert_eq!(PeriodLength::minutes(minutes), Err(expected));
+    }
+
+    #[rstest]
+    #[case(30, Ok(2880))]
+    #[case(7, Err(PeriodLengthError::DoesNotDivideDay(7)))]
+    fn test_seconds(#[case] seconds: i32, #[case] expected: Result<i32, PeriodLengthError>) {
+        assert_eq!(PeriodLength::seconds(seconds).map(|l| l.periods_per_day()), expected);
+    }
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(PeriodLength::minutes(5), Ok(PeriodLength::FIVE_MINUTES));
+        assert_eq!(PeriodLength::minutes(15), Ok(PeriodLength::FIFTEEN_MINUTES));
+        assert_eq!(PeriodLength::minutes(30), Ok(PeriodLength::THIRTY_MINUTES));
+        assert_eq!(PeriodLength::minutes(60), Ok(PeriodLength::SIXTY_MINUTES));
+        assert_eq!(PeriodLength::FIFTEEN_MINUTES.periods_in(Duration::seconds(1799)), 1);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::datetimerange::{DateTimeRange, PeriodLength};
+use chrono::{Duration, NaiveDateTime, NaiveTime, Timelike};
+
+/// How a time-clock punch is rounded to an increment such as 6, 10 or 15 minutes.
+/// Increments line up with midnight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PunchRounding {
+    /// Keep the punch as it is.
+    #[default]
+    None,
+    /// Round to the nearest increment, with exact halves going up. Halves are measured to the
+    /// millisecond, so with 15 minutes 9:07:29 rounds to 9:00 and 9:07:30 rounds to 9:15.
+    /// Punches on whole minutes follow the "7/8 rule": 9:07 rounds down and 9:08 rounds up.
+    Nearest(PeriodLength),
+    /// Always round up to the next increment.
+    Up(PeriodLength),
+    /// Always round down to the previous increment.
+    Down(PeriodLength),
+}
+
+impl PunchRounding {
+    pub fn apply(&self, punch: NaiveDateTime) -> NaiveDateTime {
+        let increment = match self {
+            PunchRounding::None => return punch,
+            PunchRounding::Nearest(increment) | PunchRounding::Up(increment) | PunchRounding::Down(increment) => {
+                increment.as_seconds() as i64 * 1_000
+            }
+        };
+
+        let time = punch.time();
+        let milliseconds =
+            time.num_seconds_from_midnight() as i64 * 1_000 + (time.nanosecond() % 1_000_000_000) as i64 / 1_000_000;
+        let remainder = milliseconds % increment;
+        let down = milliseconds - remainder;
+
+        let rounded = match self {
+            PunchRounding::Nearest(_) if remainder * 2 >= increment => down + increment,
+            PunchRounding::Up(_) if remainder > 0 => down + increment,
+            _ => down,
+        };
+        punch.date().and_time(NaiveTime::MIN) + Duration::milliseconds(rounded)
+    }
+}
+
+/// Windows around the scheduled start and end in which a punch is moved to the scheduled time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GracePeriods {
+    early_in: Duration,
+    late_in: Duration,
+    early_out: Duration,
+    late_out: Duration,
+}
+
+impl GracePeriods {
+    pub fn of(early_in: Duration, late_in: Duration, early_out: Duration, late_out: Duration) -> Self {
+        Self {
+            early_in,
+            late_in,
+            early_out,
+            late_out,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::of(Duration::zero(), Duration::zero(), Duration::zero(), Duration::zero())
+    }
+
+    pub fn early_in(&self) -> Duration {
+        self.early_in
+    }
+
+    pub fn late_in(&self) -> Duration {
+        self.late_in
+    }
+
+    pub fn early_out(&self) -> Duration {
+        self.early_out
+    }
+
+    pub fn late_out(&self) -> Duration {
+        self.late_out
+    }
+}
+
+impl Default for GracePeriods {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Why a punch was moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AdjustmentReason {
+    Unchanged,
+    /// Moved to the scheduled time because it was inside a grace period.
+    Grace,
+    /// Rounded to an increment.
+    Rounded,
+    /// Moved to the clock-in because rounding put it before the clock-in.
+    Clamped,
+}
+
+/// A record of how one punch was adjusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PunchAdjustment {
+    original: NaiveDateTime,
+    adjusted: NaiveDateTime,
+    reason: AdjustmentReason,
+}
+
+impl PunchAdjustment {
+    fn of(original: NaiveDateTime, adjusted: NaiveDateTime, reason: AdjustmentReason) -> Self {
+        Self {
+            original,
+            adjusted,
+            reason: if adjusted == original {
+                AdjustmentReason::Unchanged
+            } else {
+                reason
+            },
+        }
+    }
+
+    pub fn original(&self) -> NaiveDateTime {
+        self.original
+    }
+
+    pub fn adjusted(&self) -> NaiveDateTime {
+        self.adjusted
+    }
+
+    pub fn reason(&self) -> AdjustmentReason {
+        self.reason
+    }
+
+    /// Get how far the punch moved, positive when it moved later.
+    pub fn difference(&self) -> Duration {
+        self.adjusted - self.original
+    }
+}
+
+/// A rounded pair of punches and the adjustments that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoundedPunches {
+    range: DateTimeRange,
+    clock_in: PunchAdjustment,
+    clock_out: PunchAdjustment,
+}
+
+impl RoundedPunches {
+    pub fn range(&self) -> &DateTimeRange {
+        &self.range
+    }
+
+    pub fn clock_in(&self) -> &PunchAdjustment {
+        &self.clock_in
+    }
+
+    pub fn clock_out(&self) -> &PunchAdjustment {
+        &self.clock_out
+    }
+
+    /// Get how much the worked time changed, positive when it grew.
+    pub fn duration_difference(&self) -> Duration {
+        self.clock_out.difference() - self.clock_in.difference()
+    }
+}
+
+/// The rounding rules for clock-in and clock-out punches.
+///
+/// When a punch falls inside a grace period around the scheduled start or end it is moved
+/// to the scheduled time; otherwise it is rounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PunchRoundingPolicy {
+    clock_in: PunchRounding,
+    clock_out: PunchRounding,
+    grace: GracePeriods,
+}
+
+impl PunchRoundingPolicy {
+    pub fn of(clock_in: PunchRounding, clock_out: PunchRounding, grace: GracePeriods) -> Self {
+        Self {
+            clock_in,
+            clock_out,
+            grace,
+        }
+    }
+
+    /// Round both punches to the nearest increment, with no grace periods.
+    pub fn nearest(increment: PeriodLength) -> Self {
+        Self::of(
+            PunchRounding::Nearest(increment),
+            PunchRounding::Nearest(increment),
+            GracePeriods::none(),
+        )
+    }
+
+    pub fn clock_in(&self) -> PunchRounding {
+        self.clock_in
+    }
+
+    pub fn clock_out(&self) -> PunchRounding {
+        self.clock_out
+    }
+
+    pub fn grace(&self) -> GracePeriods {
+        self.grace
+    }
+
+    pub fn round_clock_in(&self, punch: NaiveDateTime, scheduled_start: Option<NaiveDateTime>) -> PunchAdjustment {
+        adjust(
+            punch,
+            scheduled_start,
+            self.grace.early_in,
+            self.grace.late_in,
+            self.clock_in,
+        )
+    }
+
+    pub fn round_clock_out(&self, punch: NaiveDateTime, scheduled_end: Option<NaiveDateTime>) -> PunchAdjustment {
+        adjust(
+            punch,
+            scheduled_end,
+            self.grace.early_out,
+            self.grace.late_out,
+            self.clock_out,
+        )
+    }
+
+    /// Round the punches of a shift. If rounding would put the clock-out before the clock-in,
+    /// the clock-out is moved to the clock-in and marked `Clamped`.
+    pub fn round_range(&self, punches: &DateTimeRange, scheduled: Option<&DateTimeRange>) -> RoundedPunches {
+        let clock_in = self.round_clock_in(punches.start(), scheduled.map(|range| range.start()));
+        let mut clock_out = self.round_clock_out(punches.end(), scheduled.map(|range| range.end()));
+        if clock_out.adjusted < clock_in.adjusted {
+            clock_out = PunchAdjustment::of(clock_out.original, clock_in.adjusted, AdjustmentReason::Clamped);
+        }
+
+        RoundedPunches {
+            range: DateTimeRange::of(clock_in.adjusted, clock_out.adjusted),
+            clock_in,
+            clock_out,
+        }
+    }
+}
+
+fn adjust(
+    punch: NaiveDateTime,
+    scheduled: Option<NaiveDateTime>,
+    early: Duration,
+    late: Duration,
+    rounding: PunchRounding,
+) -> PunchAdjustment {
+    let (adjusted, reason) = match scheduled {
+        Some(scheduled) if punch >= scheduled - early && punch <= scheduled + late => {
+            (scheduled, AdjustmentReason::Grace)
+        }
+        _ => (rounding.apply(punch), AdjustmentReason::Rounded),
+    };
+
+    PunchAdjustment::of(punch, adjusted, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 18)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn minutes(minutes: i32) -> PeriodLength {
+        PeriodLength::minutes(minutes).unwrap()
+    }
+
+    #[rstest]
+    #[case(PunchRounding::Nearest(minutes(15)), at(9, 7, 0), at(9, 0, 0))]
+    #[case(PunchRounding::Nearest(minutes(15)), at(9, 7, 29), at(9, 0, 0))]
+    #[case(PunchRounding::Nearest(minutes(15)), at(9, 7, 30), at(9, 15, 0))]
+    #[case(PunchRounding::Nearest(minutes(15)), at(9, 8, 0), at(9, 15, 0))]
+    #[case(PunchRounding::Nearest(minutes(15)), at(23, 53, 0), at(0, 0, 0) + Duration::days(1))]
+    #[case(PunchRounding::Nearest(minutes(6)), at(9, 2, 59), at(9, 0, 0))]
+    #[case(PunchRounding::Nearest(minutes(6)), at(9, 3, 0), at(9, 6, 0))]
+    #[case(PunchRounding::Nearest(minutes(10)), at(9, 14, 0), at(9, 10, 0))]
+    #[case(PunchRounding::Up(minutes(15)), at(9, 0, 1), at(9, 15, 0))]
+    #[case(PunchRounding::Up(minutes(15)), at(9, 15, 0), at(9, 15, 0))]
+    #[case(PunchRounding::Down(minutes(15)), at(9, 14, 59), at(9, 0, 0))]
+    #[case(PunchRounding::None, at(9, 14, 59), at(9, 14, 59))]
+    fn test_apply(#[case] rounding: PunchRounding, #[case] punch: NaiveDateTime, #[case] expected: NaiveDateTime) {
+        assert_eq!(rounding.apply(punch), expected);
+    }
+
+    #[test]
+    fn test_round_range() {
+        let policy = PunchRoundingPolicy::nearest(minutes(15));
+        let rounded = policy.round_range(&DateTimeRange::of(at(8, 53, 0), at(17, 7, 0)), None);
+
+        assert_eq!(rounded.range(), &DateTimeRange::of(at(9, 0, 0), at(17, 0, 0)));
+        assert_eq!(rounded.clock_in().reason(), AdjustmentReason::Rounded);
+        assert_eq!(rounded.clock_in().difference(), Duration::minutes(7));
+        assert_eq!(rounded.clock_out().difference(), Duration::minutes(-7));
+        assert_eq!(rounded.duration_difference(), Duration::minutes(-14));
+    }
+
+    #[rstest]
+    #[case(at(8, 55, 0), at(9, 0, 0), AdjustmentReason::Grace)]
+    #[case(at(8, 54, 59), at(8, 55, 0), AdjustmentReason::Rounded)]
+    #[case(at(9, 3, 0), at(9, 0, 0), AdjustmentReason::Grace)]
+    #[case(at(9, 4, 0), at(9, 5, 0), AdjustmentReason::Rounded)]
+    #[case(at(9, 0, 0), at(9, 0, 0), AdjustmentReason::Unchanged)]
+    fn test_round_clock_in_with_grace(
+        #[case] punch: NaiveDateTime,
+        #[case] expected: NaiveDateTime,
+        #[case] expected_reason: AdjustmentReason,
+    ) {
+        let grace = GracePeriods::of(
+            Duration::minutes(5),
+            Duration::minutes(3),
+            Duration::zero(),
+            Duration::zero(),
+        );
+        let policy = PunchRoundingPolicy::of(PunchRounding::Up(minutes(5)), PunchRounding::None, grace);
+        let adjustment = policy.round_clock_in(punch, Some(at(9, 0, 0)));
+
+        assert_eq!(adjustment.original(), punch);
+        assert_eq!(adjustment.adjusted(), expected);
+        assert_eq!(adjustment.reason(), expected_reason);
+    }
+
+    #[test]
+    fn test_round_range_with_schedule() {
+        let grace = GracePeriods::of(
+            Duration::minutes(5),
+            Duration::minutes(5),
+            Duration::minutes(5),
+            Duration::minutes(10),
+        );
+        let policy = PunchRoundingPolicy::of(PunchRounding::Up(minutes(15)), PunchRounding::Down(minutes(15)), grace);
+        let scheduled = DateTimeRange::of(at(9, 0, 0), at(17, 0, 0));
+        let rounded = policy.round_range(&DateTimeRange::of(at(9, 4, 0), at(17, 9, 0)), Some(&scheduled));
+
+        assert_eq!(rounded.range(), &scheduled);
+        assert_eq!(rounded.clock_in().reason(), AdjustmentReason::Grace);
+        assert_eq!(rounded.clock_out().reason(), AdjustmentReason::Grace);
+    }
+
+    #[test]
+    fn test_round_range_short_shift() {
+        let policy = PunchRoundingPolicy::of(
+            PunchRounding::Up(minutes(15)),
+            PunchRounding::Down(minutes(15)),
+            GracePeriods::none(),
+        );
+        let rounded = policy.round_range(&DateTimeRange::of(at(9, 2, 0), at(9, 10, 0)), None);
+        assert_eq!(rounded.range(), &DateTimeRange::of(at(9, 15, 0), at(9, 15, 0)));
+        assert_eq!(rounded.clock_in().reason(), AdjustmentReason::Rounded);
+        assert_eq!(rounded.clock_out().reason(), AdjustmentReason::Clamped);
+        assert_eq!(rounded.clock_out().difference(), Duration::minutes(5));
+    }
+
+    #[test]
+    fn test_round_range_clamped_to_original() {
+        let policy = PunchRoundingPolicy::of(
+            PunchRounding::None,
+            PunchRounding::Down(minutes(15)),
+            GracePeriods::none(),
+        );
+        let rounded = policy.round_range(&DateTimeRange::of(at(9, 10, 0), at(9, 10, 0)), None);
+        assert_eq!(rounded.range(), &DateTimeRange::of(at(9, 10, 0), at(9, 10, 0)));
+        assert_eq!(rounded.clock_out().reason(), AdjustmentReason::Unchanged);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::datetimerange::DateTimeRange;
+use crate::timezone::{LocalTimePolicy, Zone, ZoneError};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime};
+use std::hash::{Hash, Hasher};
+
+/// A range of instants in a `Zone`.
+///
+/// Unlike `DateTimeRange`, the duration is the real elapsed time, so a range that spans
+/// a daylight saving time transition is an hour shorter or longer than its wall-clock span.
+#[derive(Debug, Clone)]
+pub struct ZonedDateTimeRange {
+    start: DateTime<FixedOffset>,
+    end: DateTime<FixedOffset>,
+    zone: Zone,
+}
+
+impl ZonedDateTimeRange {
+    /// Resolve a wall-clock range against the zone.
+    pub fn from_local(range: &DateTimeRange, zone: &Zone, policy: LocalTimePolicy) -> Result<Self, ZoneError> {
+        Ok(Self {
+            start: zone.from_local(range.start(), policy)?,
+            end: zone.from_local(range.end(), policy)?,
+            zone: zone.clone(),
+        })
+    }
+
+    /// Create a range from a range of UTC date-times.
+    pub fn from_utc(range: &DateTimeRange, zone: &Zone) -> Self {
+        Self {
+            start: zone.from_utc(range.start()),
+            end: zone.from_utc(range.end()),
+            zone: zone.clone(),
+        }
+    }
+
+    /// Create a range from wall-clock times on a date, ending on the next day if the end
+    /// time is before the start time.
+    pub fn from_time_range_on_date(
+        start_time: NaiveTime,
+        end_time: NaiveTime,
+        date: NaiveDate,
+        zone: &Zone,
+        policy: LocalTimePolicy,
+    ) -> Result<Self, ZoneError> {
+        Self::from_local(&DateTimeRange::from_time_range_on_date(start_time, end_time, date), zone, policy)
+    }
+
+    /// Create a range from local midnight on the date to local midnight on the next day.
+    pub fn all_day(date: NaiveDate, zone: &Zone, policy: LocalTimePolicy) -> Result<Self, ZoneError> {
+        Self::from_local(&DateTimeRange::all_day(date), zone, policy)
+    }
+
+    pub fn start(&self) -> DateTime<FixedOffset> {
+        self.start
+    }
+
+    pub fn end(&self) -> DateTime<FixedOffset> {
+        self.end
+    }
+
+    pub fn zone(&self) -> &Zone {
+        &self.zone
+    }
+
+    /// Get the elapsed time between the start and end.
+    pub fn duration(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// Get the range as UTC date-times.
+    pub fn to_utc(&self) -> DateTimeRange {
+        DateTimeRange::of(self.start.naive_utc(), self.end.naive_utc())
+    }
+
+    /// Get the range as wall-clock date-times in the zone.
+    pub fn to_local(&self) -> DateTimeRange {
+        DateTimeRange::of(self.start.naive_local(), self.end.naive_local())
+    }
+
+    /// Get the same instants expressed in another zone.
+    pub fn with_zone(&self, zone: &Zone) -> Self {
+        Self::from_utc(&self.to_utc(), zone)
+    }
+
+    pub fn overlaps(&self, other: &ZonedDateTimeRange) -> bool {
+        self.start <= other.end && self.end >= other.start
+    }
+
+    pub fn overlaps_exclusive(&self, other: &ZonedDateTimeRange) -> bool {
+        self.start < other.end && self.end > other.start
+    }
+
+    pub fn contains(&self, instant: DateTime<FixedOffset>) -> bool {
+        instant >= self.start && instant <= self.end
+    }
+}
+
+/// Ranges are equal when they cover the same instants, regardless of zone.
+impl PartialEq for ZonedDateTimeRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+impl Eq for ZonedDateTimeRange {}
+
+impl Hash for ZonedDateTimeRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.start.naive_utc().hash(state);
+        self.end.naive_utc().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn zone() -> Zone {
+        Zone::from_posix_tz("EST5EDT,M3.2.0,M11.1.0").unwrap()
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[rstest]
+    #[case(date(3, 8), 24)]
+    #[case(date(3, 9), 23)]
+    #[case(date(11, 2), 25)]
+    fn test_all_day_duration(#[case] date: NaiveDate, #[case] expected_hours: i64) {
+        let range = ZonedDateTimeRange::all_day(date, &zone(), LocalTimePolicy::default()).unwrap();
+        assert_eq!(range.duration(), Duration::hours(expected_hours));
+        assert_eq!(range.to_local(), DateTimeRange::all_day(date));
+    }
+
+    #[test]
+    fn test_overnight_shift_across_transition() {
+        let range =
+            ZonedDateTimeRange::from_time_range_on_date(time(22, 0), time(6, 0), date(11, 1), &zone(), LocalTimePolicy::strict())
+                .unwrap();
+
+        assert_eq!(range.duration(), Duration::hours(9));
+        assert_eq!(
+            range.to_utc(),
+            DateTimeRange::of(date(11, 2).and_time(time(2, 0)), date(11, 2).and_time(time(11, 0)))
+        );
+    }
+
+    #[test]
+    fn test_from_local_rejects_gap_with_strict_policy() {
+        let range = DateTimeRange::of(date(3, 9).and_time(time(2, 30)), date(3, 9).and_time(time(8, 0)));
+        assert_eq!(
+            ZonedDateTimeRange::from_local(&range, &zone(), LocalTimePolicy::strict()),
+            Err(ZoneError::NonexistentLocalTime(date(3, 9).and_time(time(2, 30))))
+        );
+    }
+
+    #[test]
+    fn test_with_zone() {
+        let utc = DateTimeRange::of(date(7, 1).and_time(time(13, 0)), date(7, 1).and_time(time(21, 0)));
+        let range = ZonedDateTimeRange::from_utc(&utc, &zone());
+        let fixed = range.with_zone(&Zone::utc());
+
+        assert_eq!(range, fixed);
+        assert_eq!(range.to_local(), DateTimeRange::of(date(7, 1).and_time(time(9, 0)), date(7, 1).and_time(time(17, 0))));
+        assert_eq!(fixed.to_local(), utc);
+    }
+}
@@ -1,5 +1,5 @@
 use bigdecimal::BigDecimal;
-use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use num_traits::FromPrimitive;
 use std::cmp::{max, min};
 
@@ -81,6 +81,16 @@ fn last_day_of_month_year(month: u32, year: i32) -> u32 {
         .day()
 }
 
+/// Get the business date a date-time belongs to when business days start at `day_start`.
+/// Times before `day_start` belong to the previous business date.
+pub fn business_date_of(date_time: NaiveDateTime, day_start: NaiveTime) -> NaiveDate {
+    if date_time.time() < day_start {
+        date_time.date() - Duration::days(1)
+    } else {
+        date_time.date()
+    }
+}
+
 /// Return the earlier of two NaiveDateTime values.
 /// If equal, returns time1.
 pub fn earliest(time1: NaiveDateTime, time2: NaiveDateTime) -> NaiveDateTime {
@@ -301,6 +311,22 @@ mod tests {
         assert_eq!(result.day(), expected_day, "Failed for {:?}", input);
     }
 
+    #[rstest]
+    #[case(NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(0, 0, 0).unwrap(), 0, 2025, 8, 20)]
+    #[case(NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(3, 59, 59).unwrap(), 4, 2025, 8, 19)]
+    #[case(NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(4, 0, 0).unwrap(), 4, 2025, 8, 20)]
+    #[case(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap().and_hms_opt(2, 30, 0).unwrap(), 4, 2024, 12, 31)]
+    fn test_business_date_of(
+        #[case] input: NaiveDateTime,
+        #[case] day_start_hour: u32,
+        #[case] expected_year: i32,
+        #[case] expected_month: u32,
+        #[case] expected_day: u32,
+    ) {
+        let result = business_date_of(input, NaiveTime::from_hms_opt(day_start_hour, 0, 0).unwrap());
+        assert_eq!(result, NaiveDate::from_ymd_opt(expected_year, expected_month, expected_day).unwrap(), "Failed for {:?}", input);
+    }
+
     #[rstest]
     #[case(NaiveDate::from_ymd_opt(2026, 8, 20).unwrap(), 1, 2025, 8, 20)]
     #[case(NaiveDate::from_ymd_opt(2027, 12, 31).unwrap(), 2, 2025, 12, 31)]
@@ -0,0 +1,600 @@
+use crate::daterange::DateRange;
+use crate::datetimerange::DateTimeRange;
+use chrono::{NaiveDate, NaiveDateTime};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// A closed interval that can be stored in an `IntervalIndex`.
+pub trait Interval {
+    type Point: Ord + Copy;
+
+    /// Get the lower bound of the interval.
+    fn lower(&self) -> Self::Point;
+
+    /// Get the upper bound of the interval.
+    fn upper(&self) -> Self::Point;
+}
+
+impl Interval for DateTimeRange {
+    type Point = NaiveDateTime;
+
+    fn lower(&self) -> NaiveDateTime {
+        self.start()
+    }
+
+    fn upper(&self) -> NaiveDateTime {
+        self.end()
+    }
+}
+
+impl Interval for DateRange {
+    type Point = NaiveDate;
+
+    fn lower(&self) -> NaiveDate {
+        self.start_date()
+    }
+
+    fn upper(&self) -> NaiveDate {
+        self.end_date()
+    }
+}
+
+/// An `IntervalIndex` keyed by `DateTimeRange`.
+pub type DateTimeRangeIndex<T> = IntervalIndex<DateTimeRange, T>;
+
+/// An `IntervalIndex` keyed by `DateRange`.
+pub type DateRangeIndex<T> = IntervalIndex<DateRange, T>;
+
+/// An entry stored in an `IntervalIndex`.
+pub type IntervalEntry<'a, K, T> = (&'a K, &'a T);
+
+/// An augmented AVL tree of intervals supporting fast point and overlap queries.
+///
+/// Overlap queries follow the same rules as `DateTimeRange::overlaps` (inclusive) and
+/// `DateTimeRange::overlaps_exclusive`. Entries are ordered by lower bound, then upper
+/// bound, then insertion order, so several entries may share the same key.
+pub struct IntervalIndex<K: Interval, T> {
+    root: Link<K, T>,
+    len: usize,
+    next_seq: u64,
+}
+
+type Link<K, T> = Option<Box<Node<K, T>>>;
+
+struct Node<K: Interval, T> {
+    key: K,
+    value: T,
+    seq: u64,
+    max_upper: K::Point,
+    height: i32,
+    left: Link<K, T>,
+    right: Link<K, T>,
+}
+
+impl<K: Interval, T> IntervalIndex<K, T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// Build a balanced index from a batch of entries in O(n log n).
+    pub fn from_entries(entries: Vec<(K, T)>) -> Self {
+        let mut entries = entries;
+        entries.sort_by(|a, b| compare_bounds(&a.0, &b.0));
+
+        let len = entries.len();
+        let mut iter = entries
+            .into_iter()
+            .enumerate()
+            .map(|(seq, (key, value))| (key, value, seq as u64));
+
+        Self {
+            root: build(&mut iter, len),
+            len,
+            next_seq: len as u64,
+        }
+    }
+
+    /// Get the number of entries in the index.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the index has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add an entry to the index.
+    pub fn insert(&mut self, key: K, value: T) {
+        let node = Box::new(Node::new(key, value, self.next_seq));
+        self.next_seq += 1;
+        self.len += 1;
+        self.root = Some(insert_node(self.root.take(), node));
+    }
+
+    /// Remove one entry with the specified key, returning its value.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        self.remove_where(key, |_| true)
+    }
+
+    /// Remove the entry with the specified key and value. Returns true if an entry was removed.
+    pub fn remove_entry(&mut self, key: &K, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.remove_where(key, |v| v == value).is_some()
+    }
+
+    fn remove_where(&mut self, key: &K, mut predicate: impl FnMut(&T) -> bool) -> Option<T> {
+        let seq = find_seq(&self.root, key.lower(), key.upper(), &mut predicate)?;
+        let (root, removed) = remove_node(self.root.take(), key.lower(), key.upper(), seq);
+        self.root = root;
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    /// Get an iterator over the entries ordered by lower bound, then upper bound.
+    pub fn iter(&self) -> IntervalIndexIter<'_, K, T> {
+        let mut iter = IntervalIndexIter { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    /// Get the entries whose interval contains the point (inclusive).
+    pub fn containing(&self, point: K::Point) -> Vec<IntervalEntry<'_, K, T>> {
+        let mut result = Vec::new();
+        collect_overlapping(&self.root, point, point, false, &mut result);
+        result
+    }
+
+    /// Get the entries that overlap the interval, including entries that only touch it.
+    pub fn overlapping(&self, interval: &K) -> Vec<IntervalEntry<'_, K, T>> {
+        let mut result = Vec::new();
+        collect_overlapping(&self.root, interval.lower(), interval.upper(), false, &mut result);
+        result
+    }
+
+    /// Get the entries that overlap the interval, excluding entries that only touch it.
+    pub fn overlapping_exclusive(&self, interval: &K) -> Vec<IntervalEntry<'_, K, T>> {
+        let mut result = Vec::new();
+        collect_overlapping(&self.root, interval.lower(), interval.upper(), true, &mut result);
+        result
+    }
+
+    /// Get every pair of entries that overlap, including pairs that only touch.
+    ///
+    /// Runs in O(n log n + k) where k is the number of pairs. The first entry of each
+    /// pair sorts before the second.
+    pub fn overlapping_pairs(&self) -> Vec<(IntervalEntry<'_, K, T>, IntervalEntry<'_, K, T>)> {
+        self.pairs(false)
+    }
+
+    /// Get every pair of entries that overlap, excluding pairs that only touch.
+    pub fn overlapping_pairs_exclusive(&self) -> Vec<(IntervalEntry<'_, K, T>, IntervalEntry<'_, K, T>)> {
+        self.pairs(true)
+    }
+
+    fn pairs(&self, exclusive: bool) -> Vec<(IntervalEntry<'_, K, T>, IntervalEntry<'_, K, T>)> {
+        let entries: Vec<IntervalEntry<'_, K, T>> = self.iter().collect();
+        let mut result = Vec::new();
+
+        // Sweep by lower bound, keeping the entries whose upper bound has not yet been passed.
+        let mut active: Vec<usize> = Vec::new();
+        let mut positions = vec![usize::MAX; entries.len()];
+        let mut expiring = BinaryHeap::new();
+
+        for (index, (key, _)) in entries.iter().enumerate() {
+            let lower = key.lower();
+
+            while let Some(Reverse((upper, expired))) = expiring.peek().copied() {
+                let passed = if exclusive { upper <= lower } else { upper < lower };
+                if !passed {
+                    break;
+                }
+                expiring.pop();
+
+                let position = positions[expired];
+                active.swap_remove(position);
+                if position < active.len() {
+                    positions[active[position]] = position;
+                }
+            }
+
+            for &other in &active {
+                if !exclusive || entries[other].0.lower() < key.upper() {
+                    result.push((entries[other], entries[index]));
+                }
+            }
+
+            positions[index] = active.len();
+            active.push(index);
+            expiring.push(Reverse((key.upper(), index)));
+        }
+
+        result
+    }
+}
+
+impl<K: Interval, T> Default for IntervalIndex<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Interval, T> FromIterator<(K, T)> for IntervalIndex<K, T> {
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
+        Self::from_entries(iter.into_iter().collect())
+    }
+}
+
+/// In-order iterator over the entries of an `IntervalIndex`.
+pub struct IntervalIndexIter<'a, K: Interval, T> {
+    stack: Vec<&'a Node<K, T>>,
+}
+
+impl<'a, K: Interval, T> IntervalIndexIter<'a, K, T> {
+    fn push_left(&mut self, mut node: Option<&'a Node<K, T>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, K: Interval, T> Iterator for IntervalIndexIter<'a, K, T> {
+    type Item = IntervalEntry<'a, K, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.key, &node.value))
+    }
+}
+
+impl<K: Interval, T> Node<K, T> {
+    fn new(key: K, value: T, seq: u64) -> Self {
+        let max_upper = key.upper();
+        Self {
+            key,
+            value,
+            seq,
+            max_upper,
+            height: 1,
+            left: None,
+            right: None,
+        }
+    }
+
+    fn compare(&self, lower: K::Point, upper: K::Point, seq: u64) -> Ordering {
+        (self.key.lower(), self.key.upper(), self.seq).cmp(&(lower, upper, seq))
+    }
+
+    fn update(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.max_upper = self.key.upper();
+        for child in [&self.left, &self.right].into_iter().flatten() {
+            self.max_upper = self.max_upper.max(child.max_upper);
+        }
+    }
+}
+
+fn compare_bounds<K: Interval>(a: &K, b: &K) -> Ordering {
+    (a.lower(), a.upper()).cmp(&(b.lower(), b.upper()))
+}
+
+fn height<K: Interval, T>(node: &Link<K, T>) -> i32 {
+    node.as_ref().map_or(0, |n| n.height)
+}
+
+fn build<K: Interval, T>(entries: &mut impl Iterator<Item = (K, T, u64)>, count: usize) -> Link<K, T> {
+    if count == 0 {
+        return None;
+    }
+
+    let left = build(entries, count / 2);
+    let (key, value, seq) = entries.next()?;
+    let mut node = Box::new(Node::new(key, value, seq));
+    node.left = left;
+    node.right = build(entries, count - count / 2 - 1);
+    node.update();
+    Some(node)
+}
+
+fn rotate_left<K: Interval, T>(mut node: Box<Node<K, T>>) -> Box<Node<K, T>> {
+    let mut pivot = node.right.take().unwrap();
+    node.right = pivot.left.take();
+    node.update();
+    pivot.left = Some(node);
+    pivot.update();
+    pivot
+}
+
+fn rotate_right<K: Interval, T>(mut node: Box<Node<K, T>>) -> Box<Node<K, T>> {
+    let mut pivot = node.left.take().unwrap();
+    node.left = pivot.right.take();
+    node.update();
+    pivot.right = Some(node);
+    pivot.update();
+    pivot
+}
+
+fn rebalance<K: Interval, T>(mut node: Box<Node<K, T>>) -> Box<Node<K, T>> {
+    node.update();
+    let balance = height(&node.left) - height(&node.right);
+
+    if balance > 1 {
+        let left = node.left.take().unwrap();
+        node.left = Some(if height(&left.left) < height(&left.right) { rotate_left(left) } else { left });
+        rotate_right(node)
+    } else if balance < -1 {
+        let right = node.right.take().unwrap();
+        node.right = Some(if height(&right.right) < height(&right.left) { rotate_right(right) } else { right });
+        rotate_left(node)
+    } else {
+        node
+    }
+}
+
+fn insert_node<K: Interval, T>(node: Link<K, T>, new: Box<Node<K, T>>) -> Box<Node<K, T>> {
+    match node {
+        None => new,
+        Some(mut n) => {
+            if n.compare(new.key.lower(), new.key.upper(), new.seq) == Ordering::Greater {
+                n.left = Some(insert_node(n.left.take(), new));
+            } else {
+                n.right = Some(insert_node(n.right.take(), new));
+            }
+            rebalance(n)
+        }
+    }
+}
+
+fn find_seq<K: Interval, T>(
+    node: &Link<K, T>,
+    lower: K::Point,
+    upper: K::Point,
+    predicate: &mut impl FnMut(&T) -> bool,
+) -> Option<u64> {
+    let n = node.as_ref()?;
+    match (n.key.lower(), n.key.upper()).cmp(&(lower, upper)) {
+        Ordering::Greater => find_seq(&n.left, lower, upper, predicate),
+        Ordering::Less => find_seq(&n.right, lower, upper, predicate),
+        Ordering::Equal => {
+            if predicate(&n.value) {
+                Some(n.seq)
+            } else {
+                find_seq(&n.left, lower, upper, predicate).or_else(|| find_seq(&n.right, lower, upper, predicate))
+            }
+        }
+    }
+}
+
+fn remove_min<K: Interval, T>(mut node: Box<Node<K, T>>) -> (Link<K, T>, Box<Node<K, T>>) {
+    match node.left.take() {
+        None => (node.right.take(), node),
+        Some(left) => {
+            let (left, min) = remove_min(left);
+            node.left = left;
+            (Some(rebalance(node)), min)
+        }
+    }
+}
+
+fn remove_node<K: Interval, T>(
+    node: Link<K, T>,
+    lower: K::Point,
+    upper: K::Point,
+    seq: u64,
+) -> (Link<K, T>, Option<T>) {
+    let mut n = match node {
+        None => return (None, None),
+        Some(n) => n,
+    };
+
+    match n.compare(lower, upper, seq) {
+        Ordering::Greater => {
+            let (left, removed) = remove_node(n.left.take(), lower, upper, seq);
+            n.left = left;
+            (Some(rebalance(n)), removed)
+        }
+        Ordering::Less => {
+            let (right, removed) = remove_node(n.right.take(), lower, upper, seq);
+            n.right = right;
+            (Some(rebalance(n)), removed)
+        }
+        Ordering::Equal => {
+            let left = n.left.take();
+            let right = n.right.take();
+            let replacement = match (left, right) {
+                (None, right) => right,
+                (left, None) => left,
+                (left, Some(right)) => {
+                    let (right, mut successor) = remove_min(right);
+                    successor.left = left;
+                    successor.right = right;
+                    Some(rebalance(successor))
+                }
+            };
+            (replacement, Some(n.value))
+        }
+    }
+}
+
+fn collect_overlapping<'a, K: Interval, T>(
+    node: &'a Link<K, T>,
+    lower: K::Point,
+    upper: K::Point,
+    exclusive: bool,
+    result: &mut Vec<IntervalEntry<'a, K, T>>,
+) {
+    let n = match node {
+        None => return,
+        Some(n) => n,
+    };
+
+    let subtree_ends_before = if exclusive { n.max_upper <= lower } else { n.max_upper < lower };
+    if subtree_ends_before {
+        return;
+    }
+
+    collect_overlapping(&n.left, lower, upper, exclusive, result);
+
+    let starts_after = if exclusive { n.key.lower() >= upper } else { n.key.lower() > upper };
+    if starts_after {
+        return;
+    }
+
+    let ends_before = if exclusive { n.key.upper() <= lower } else { n.key.upper() < lower };
+    if !ends_before {
+        result.push((&n.key, &n.value));
+    }
+
+    collect_overlapping(&n.right, lower, upper, exclusive, result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn at(hour: i64) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, 20).unwrap().and_hms_opt(0, 0, 0).unwrap() + Duration::hours(hour)
+    }
+
+    fn range(start: i64, end: i64) -> DateTimeRange {
+        DateTimeRange::of(at(start), at(end))
+    }
+
+    fn shifts() -> Vec<(DateTimeRange, usize)> {
+        // Simple LCG so the data is deterministic
+        let mut seed: u64 = 42;
+        let mut next = move |bound: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % bound
+        };
+
+        (0..300)
+            .map(|i| {
+                let start = next(200) as i64;
+                let length = next(12) as i64;
+                (range(start, start + length), i)
+            })
+            .collect()
+    }
+
+    fn sorted_ids(entries: Vec<IntervalEntry<'_, DateTimeRange, usize>>) -> Vec<usize> {
+        let mut ids: Vec<usize> = entries.into_iter().map(|(_, id)| *id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_queries_match_brute_force() {
+        let shifts = shifts();
+        let index: DateTimeRangeIndex<usize> = shifts.iter().cloned().collect();
+
+        assert_eq!(index.len(), shifts.len());
+
+        for query in [range(0, 5), range(50, 50), range(100, 130), range(190, 220), range(250, 260)] {
+            let expected: Vec<usize> = shifts.iter().filter(|(r, _)| r.overlaps(&query)).map(|(_, id)| *id).collect();
+            assert_eq!(sorted_ids(index.overlapping(&query)), expected);
+
+            let expected: Vec<usize> = shifts.iter().filter(|(r, _)| r.overlaps_exclusive(&query)).map(|(_, id)| *id).collect();
+            assert_eq!(sorted_ids(index.overlapping_exclusive(&query)), expected);
+
+            let expected: Vec<usize> = shifts.iter().filter(|(r, _)| r.contains(query.start())).map(|(_, id)| *id).collect();
+            assert_eq!(sorted_ids(index.containing(query.start())), expected);
+        }
+    }
+
+    #[test]
+    fn test_overlapping_pairs_match_brute_force() {
+        let shifts = shifts();
+        let mut index = DateTimeRangeIndex::new();
+        for (r, id) in &shifts {
+            index.insert(r.clone(), *id);
+        }
+
+        for exclusive in [false, true] {
+            let mut expected = Vec::new();
+            for (i, (a, id_a)) in shifts.iter().enumerate() {
+                for (b, id_b) in &shifts[i + 1..] {
+                    let overlaps = if exclusive { a.overlaps_exclusive(b) } else { a.overlaps(b) };
+                    if overlaps {
+                        expected.push((*id_a.min(id_b), *id_a.max(id_b)));
+                    }
+                }
+            }
+            expected.sort();
+
+            let pairs = if exclusive { index.overlapping_pairs_exclusive() } else { index.overlapping_pairs() };
+            let mut actual: Vec<(usize, usize)> =
+                pairs.into_iter().map(|((_, a), (_, b))| (*a.min(b), *a.max(b))).collect();
+            actual.sort();
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut index = DateTimeRangeIndex::new();
+        index.insert(range(9, 17), "a");
+        index.insert(range(9, 17), "b");
+        index.insert(range(17, 22), "c");
+        index.insert(range(1, 3), "d");
+
+        assert_eq!(index.overlapping_exclusive(&range(16, 18)).len(), 3);
+        assert!(index.remove_entry(&range(9, 17), &"b"));
+        assert!(!index.remove_entry(&range(9, 17), &"b"));
+        assert_eq!(index.remove(&range(1, 3)), Some("d"));
+        assert_eq!(index.remove(&range(1, 3)), None);
+        assert_eq!(index.len(), 2);
+
+        let values: Vec<&str> = index.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec!["a", "c"]);
+        assert_eq!(index.overlapping_pairs().len(), 1);
+        assert!(index.overlapping_pairs_exclusive().is_empty());
+    }
+
+    #[test]
+    fn test_remove_keeps_queries_consistent() {
+        let shifts = shifts();
+        let mut index: DateTimeRangeIndex<usize> = shifts.iter().cloned().collect();
+
+        for (r, id) in shifts.iter().filter(|(_, id)| id % 3 == 0) {
+            assert!(index.remove_entry(r, id));
+        }
+
+        let query = range(80, 120);
+        let expected: Vec<usize> = shifts
+            .iter()
+            .filter(|(r, id)| id % 3 != 0 && r.overlaps(&query))
+            .map(|(_, id)| *id)
+            .collect();
+        assert_eq!(sorted_ids(index.overlapping(&query)), expected);
+        assert_eq!(index.len(), 200);
+    }
+
+    #[test]
+    fn test_date_range_index() {
+        let date = |d| NaiveDate::from_ymd_opt(2025, 8, d).unwrap();
+        let index: DateRangeIndex<&str> = vec![
+            (DateRange::new(date(1), date(7)), "week1"),
+            (DateRange::new(date(8), date(14)), "week2"),
+            (DateRange::new(date(5), date(10)), "vacation"),
+        ]
+        .into_iter()
+        .collect();
+
+        let values: Vec<&str> = index.containing(date(7)).into_iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec!["week1", "vacation"]);
+        assert_eq!(index.overlapping_pairs().len(), 2);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod interval_index;
+pub use interval_index::*;
@@ -1,6 +1,9 @@
 pub mod daterange;
 pub mod datetimerange;
 pub mod dateutils;
+pub mod intervalindex;
+pub mod schedule;
 pub mod timerange;
+pub mod timezone;
 pub mod variance;
 pub mod numbers;
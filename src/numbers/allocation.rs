@@ -0,0 +1,169 @@
+use crate::daterange::DateRange;
+use bigdecimal::num_bigint::BigInt;
+use bigdecimal::{BigDecimal, Signed, Zero};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AllocationError {
+    NoWeights,
+    /// The weight at the index is negative.
+    NegativeWeight(usize),
+    ZeroTotalWeight,
+    /// The total has more decimal places than the parts are allocated with.
+    TooManyDecimals(BigDecimal),
+}
+
+impl fmt::Display for AllocationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AllocationError::NoWeights => write!(f, "cannot allocate across no weights"),
+            AllocationError::NegativeWeight(index) => write!(f, "weight {} is negative", index),
+            AllocationError::ZeroTotalWeight => write!(f, "weights add up to zero"),
+            AllocationError::TooManyDecimals(total) => {
+                write!(f, "total {} has more decimal places than the allocated parts", total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AllocationError {}
+
+/// Split the total into parts proportional to the weights, rounded to `number_decimals`,
+/// that add up exactly to the total.
+///
+/// Uses the largest-remainder method: each part is first rounded toward zero, then the
+/// units left over go one at a time to the parts with the largest remainders. Ties go to the
+/// earlier part, so the same input always gives the same result.
+pub fn allocate(
+    total: &BigDecimal,
+    weights: &[BigDecimal],
+    number_decimals: usize,
+) -> Result<Vec<BigDecimal>, AllocationError> {
+    if weights.is_empty() {
+        return Err(AllocationError::NoWeights);
+    }
+    if let Some(index) = weights.iter().position(|weight| weight.is_negative()) {
+        return Err(AllocationError::NegativeWeight(index));
+    }
+
+    let scale = number_decimals as i64;
+    if total.normalized().fractional_digit_count() > scale {
+        return Err(AllocationError::TooManyDecimals(total.clone()));
+    }
+
+    let weight_scale = weights
+        .iter()
+        .map(|weight| weight.fractional_digit_count())
+        .max()
+        .unwrap_or(0)
+        .max(0);
+    let weights: Vec<BigInt> = weights.iter().map(|weight| to_units(weight, weight_scale)).collect();
+    let total_weight: BigInt = weights.iter().sum();
+    if total_weight.is_zero() {
+        return Err(AllocationError::ZeroTotalWeight);
+    }
+
+    let units = to_units(total, scale);
+    let negative = units.is_negative();
+    let units = units.abs();
+
+    let mut parts = Vec::with_capacity(weights.len());
+    let mut remainders = Vec::with_capacity(weights.len());
+    for (index, weight) in weights.iter().enumerate() {
+        let share = &units * weight;
+        parts.push(&share / &total_weight);
+        remainders.push((share % &total_weight, index));
+    }
+
+    let allocated: BigInt = parts.iter().sum();
+    let mut left_over = &units - allocated;
+    remainders.sort_by(|(a, a_index), (b, b_index)| b.cmp(a).then(a_index.cmp(b_index)));
+    for (_, index) in remainders {
+        if !left_over.is_positive() {
+            break;
+        }
+        parts[index] += 1;
+        left_over -= 1;
+    }
+
+    Ok(parts
+        .into_iter()
+        .map(|part| BigDecimal::new(if negative { -part } else { part }, scale))
+        .collect())
+}
+
+/// Split the total across date ranges in proportion to the number of days in each.
+pub fn allocate_by_ranges(
+    total: &BigDecimal,
+    date_ranges: &[DateRange],
+    number_decimals: usize,
+) -> Result<Vec<BigDecimal>, AllocationError> {
+    let weights: Vec<BigDecimal> = date_ranges
+        .iter()
+        .map(|range| BigDecimal::from(range.len() as u64))
+        .collect();
+    allocate(total, &weights, number_decimals)
+}
+
+fn to_units(value: &BigDecimal, scale: i64) -> BigInt {
+    value.with_scale(scale).as_bigint_and_exponent().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn decimals(values: &[&str]) -> Vec<BigDecimal> {
+        values
+            .iter()
+            .map(|value| BigDecimal::from_str(value).unwrap())
+            .collect()
+    }
+
+    #[rstest]
+    #[case("100", &["1", "1", "1"], 2, &["33.34", "33.33", "33.33"])]
+    #[case("-100", &["1", "1", "1"], 2, &["-33.34", "-33.33", "-33.33"])]
+    #[case("0.05", &["1", "1", "1", "1"], 2, &["0.02", "0.01", "0.01", "0.01"])]
+    #[case("10", &["0.5", "0.3", "0.2"], 2, &["5.00", "3.00", "2.00"])]
+    #[case("1", &["1", "2", "3"], 2, &["0.17", "0.33", "0.50"])]
+    #[case("1", &["2", "1", "2"], 0, &["1", "0", "0"])]
+    #[case("1", &["1", "2", "2"], 0, &["0", "1", "0"])]
+    #[case("7", &["0", "1"], 0, &["0", "7"])]
+    #[case("1234.5678", &["3", "7"], 4, &["370.3703", "864.1975"])]
+    fn test_allocate(
+        #[case] total: &str,
+        #[case] weights: &[&str],
+        #[case] number_decimals: usize,
+        #[case] expected: &[&str],
+    ) {
+        let total = BigDecimal::from_str(total).unwrap();
+        let parts = allocate(&total, &decimals(weights), number_decimals).unwrap();
+        assert_eq!(parts, decimals(expected));
+        assert_eq!(parts.iter().sum::<BigDecimal>(), total);
+    }
+
+    #[rstest]
+    #[case("100", &[], AllocationError::NoWeights)]
+    #[case("100", &["1", "-1"], AllocationError::NegativeWeight(1))]
+    #[case("100", &["0", "0"], AllocationError::ZeroTotalWeight)]
+    #[case("100.005", &["1"], AllocationError::TooManyDecimals(BigDecimal::from_str("100.005").unwrap()))]
+    fn test_allocate_invalid(#[case] total: &str, #[case] weights: &[&str], #[case] expected: AllocationError) {
+        let total = BigDecimal::from_str(total).unwrap();
+        assert_eq!(allocate(&total, &decimals(weights), 2), Err(expected));
+    }
+
+    #[test]
+    fn test_allocate_by_ranges() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 8, day).unwrap();
+        let ranges = vec![
+            DateRange::new(date(1), date(10)),
+            DateRange::new(date(11), date(20)),
+            DateRange::new(date(21), date(31)),
+        ];
+        let parts = allocate_by_ranges(&BigDecimal::from(1000), &ranges, 2).unwrap();
+        assert_eq!(parts, decimals(&["322.58", "322.58", "354.84"]));
+    }
+}
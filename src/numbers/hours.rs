@@ -0,0 +1,351 @@
+use crate::datetimerange::DateTimeRange;
+use crate::numbers::{RoundWithMode, RoundingMode, HOURS_ROUND_PRECISION, RAW_HOURS_ROUND_PRECISION};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::Duration;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+const MILLISECONDS_PER_MINUTE: i64 = 60_000;
+const MILLISECONDS_PER_HOUR: i64 = 3_600_000;
+/// The number of digits in `i64::MAX / MILLISECONDS_PER_HOUR`.
+const MAX_WHOLE_HOUR_DIGITS: i64 = 13;
+
+/// A number of hours worked, stored as whole milliseconds so that adding up many shifts
+/// never picks up floating point error. Every 4-decimal hour value is exact.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hours {
+    milliseconds: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoursParseError(pub String);
+
+impl fmt::Display for HoursParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid number of hours", self.0)
+    }
+}
+
+impl std::error::Error for HoursParseError {}
+
+impl Hours {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn from_minutes(minutes: i64) -> Self {
+        Self {
+            milliseconds: minutes * MILLISECONDS_PER_MINUTE,
+        }
+    }
+
+    pub fn from_duration(duration: Duration) -> Self {
+        Self {
+            milliseconds: duration.num_milliseconds(),
+        }
+    }
+
+    /// Create from decimal hours such as 7.75, to the nearest millisecond.
+    pub fn from_decimal(hours: f64) -> Self {
+        Self {
+            milliseconds: (hours * MILLISECONDS_PER_HOUR as f64).round() as i64,
+        }
+    }
+
+    /// Create from minutes, or `None` if the hours are too large to store.
+    pub fn checked_from_minutes(minutes: i64) -> Option<Self> {
+        Some(Self {
+            milliseconds: minutes.checked_mul(MILLISECONDS_PER_MINUTE)?,
+        })
+    }
+
+    /// Create from exact decimal hours, to the nearest millisecond, or `None` if the hours are
+    /// too large to store.
+    pub fn from_big_decimal(hours: &BigDecimal) -> Option<Self> {
+        // Rounding a value with a large exponent writes out all of its digits, so reject
+        // anything with more whole digits than the largest storable hours first.
+        if hours.digits() as i64 - hours.fractional_digit_count() > MAX_WHOLE_HOUR_DIGITS {
+            return None;
+        }
+        let milliseconds = (hours * BigDecimal::from(MILLISECONDS_PER_HOUR)).round_with_mode(0, RoundingMode::HalfUp);
+        Some(Self {
+            milliseconds: milliseconds.to_i64()?,
+        })
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        Duration::milliseconds(self.milliseconds)
+    }
+
+    pub fn as_minutes(&self) -> i64 {
+        self.milliseconds / MILLISECONDS_PER_MINUTE
+    }
+
+    pub fn as_decimal(&self) -> f64 {
+        self.milliseconds as f64 / MILLISECONDS_PER_HOUR as f64
+    }
+
+    /// Get the decimal hours rounded to a number of decimal places.
+    pub fn as_big_decimal(&self, number_decimals: usize, mode: RoundingMode) -> BigDecimal {
+        (BigDecimal::from(self.milliseconds) / BigDecimal::from(MILLISECONDS_PER_HOUR))
+            .round_with_mode(number_decimals, mode)
+    }
+
+    /// Round to a number of decimal places of an hour. Rounding away from zero past the largest
+    /// storable hours saturates.
+    pub fn round_to(&self, number_decimals: usize, mode: RoundingMode) -> Hours {
+        Self::from_big_decimal(&self.as_big_decimal(number_decimals, mode)).unwrap_or(Hours {
+            milliseconds: if self.milliseconds < 0 { -i64::MAX } else { i64::MAX },
+        })
+    }
+
+    /// Round half up to 2 decimals, the precision of `round_hours`.
+    pub fn round_hours(&self) -> Hours {
+        self.round_to(HOURS_ROUND_PRECISION, RoundingMode::HalfUp)
+    }
+
+    /// Round half up to 4 decimals, the precision of `round_raw_hours`.
+    pub fn round_raw_hours(&self) -> Hours {
+        self.round_to(RAW_HOURS_ROUND_PRECISION, RoundingMode::HalfUp)
+    }
+
+    /// Format as `HH:MM`, dropping any seconds, e.g. `07:45` or `-01:30`. Hours are not
+    /// limited to a day, so a week can be `40:00`.
+    pub fn as_hh_mm(&self) -> String {
+        let sign = if self.milliseconds < 0 { "-" } else { "" };
+        let minutes = self.as_minutes().abs();
+        format!("{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    }
+}
+
+impl From<Duration> for Hours {
+    fn from(duration: Duration) -> Self {
+        Self::from_duration(duration)
+    }
+}
+
+impl From<&DateTimeRange> for Hours {
+    fn from(range: &DateTimeRange) -> Self {
+        Self::from_duration(range.duration())
+    }
+}
+
+impl From<Hours> for Duration {
+    fn from(hours: Hours) -> Self {
+        hours.as_duration()
+    }
+}
+
+impl Add for Hours {
+    type Output = Hours;
+
+    fn add(self, other: Hours) -> Hours {
+        Hours {
+            milliseconds: self.milliseconds + other.milliseconds,
+        }
+    }
+}
+
+impl AddAssign for Hours {
+    fn add_assign(&mut self, other: Hours) {
+        self.milliseconds += other.milliseconds;
+    }
+}
+
+impl Sub for Hours {
+    type Output = Hours;
+
+    fn sub(self, other: Hours) -> Hours {
+        Hours {
+            milliseconds: self.milliseconds - other.milliseconds,
+        }
+    }
+}
+
+impl SubAssign for Hours {
+    fn sub_assign(&mut self, other: Hours) {
+        self.milliseconds -= other.milliseconds;
+    }
+}
+
+impl Neg for Hours {
+    type Output = Hours;
+
+    fn neg(self) -> Hours {
+        Hours {
+            milliseconds: -self.milliseconds,
+        }
+    }
+}
+
+impl Sum for Hours {
+    fn sum<I: Iterator<Item = Hours>>(iter: I) -> Self {
+        iter.fold(Hours::zero(), Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Hours> for Hours {
+    fn sum<I: Iterator<Item = &'a Hours>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl fmt::Display for Hours {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_hh_mm())
+    }
+}
+
+/// Parses `HH:MM` such as `7:45` or `-01:30`, or decimal hours such as `7.75`.
+impl FromStr for Hours {
+    type Err = HoursParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || HoursParseError(text.to_string());
+        let trimmed = text.trim();
+
+        let is_number = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+
+        let Some((hours, minutes)) = trimmed.split_once(':') else {
+            let unsigned = trimmed.strip_prefix(['-', '+']).unwrap_or(trimmed);
+            let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, "0"));
+            if !is_number(whole) || !is_number(fraction) {
+                return Err(invalid());
+            }
+            let hours = BigDecimal::from_str(trimmed).map_err(|_| invalid())?;
+            return Self::from_big_decimal(&hours).ok_or_else(invalid);
+        };
+
+        let (negative, hours) = match hours.strip_prefix('-') {
+            Some(hours) => (true, hours),
+            None => (false, hours),
+        };
+        if !is_number(hours) || minutes.len() != 2 || !is_number(minutes) {
+            return Err(invalid());
+        }
+
+        let hours: i64 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+        if minutes >= 60 {
+            return Err(invalid());
+        }
+
+        let result = hours
+            .checked_mul(60)
+            .and_then(|hours| hours.checked_add(minutes))
+            .and_then(Self::checked_from_minutes)
+            .ok_or_else(invalid)?;
+        Ok(if negative { -result } else { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    #[test]
+    fn test_conversions() {
+        let hours = Hours::from_minutes(465);
+        assert_eq!(hours.as_decimal(), 7.75);
+        assert_eq!(hours.as_duration(), Duration::minutes(465));
+        assert_eq!(Hours::from_decimal(7.75), hours);
+        assert_eq!(Hours::from(Duration::minutes(465)), hours);
+        assert_eq!(Duration::from(hours), Duration::minutes(465));
+        assert_eq!(
+            hours.as_big_decimal(2, RoundingMode::HalfUp),
+            BigDecimal::from_str("7.75").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_date_time_range() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 18).unwrap();
+        let range = DateTimeRange::of(
+            date.and_hms_opt(22, 0, 0).unwrap(),
+            date.and_hms_opt(23, 20, 0).unwrap(),
+        );
+        assert_eq!(Hours::from(&range).as_hh_mm(), "01:20");
+    }
+
+    #[test]
+    fn test_sum_is_exact() {
+        // 0.1 hours is 6 minutes, so ten of them add up to exactly 1 hour
+        let hours: Vec<Hours> = (0..10).map(|_| Hours::from_decimal(0.1)).collect();
+        assert_eq!(hours.iter().sum::<Hours>(), Hours::from_minutes(60));
+        assert_eq!(hours.into_iter().sum::<Hours>().as_decimal(), 1.0);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut hours = Hours::from_minutes(90) + Hours::from_minutes(30);
+        assert_eq!(hours, Hours::from_minutes(120));
+        hours -= Hours::from_minutes(150);
+        assert_eq!(hours, Hours::from_minutes(-30));
+        assert_eq!(-hours, Hours::from_minutes(30));
+    }
+
+    #[rstest]
+    #[case(Hours::from_minutes(20), 0.33, 0.3333)]
+    #[case(Hours::from_minutes(40), 0.67, 0.6667)]
+    #[case(Hours::from_minutes(-20), -0.33, -0.3333)]
+    #[case(Hours::from_duration(Duration::seconds(18)), 0.01, 0.005)]
+    fn test_rounding_presets(#[case] hours: Hours, #[case] expected_hours: f64, #[case] expected_raw_hours: f64) {
+        assert_eq!(hours.round_hours().as_decimal(), expected_hours);
+        assert_eq!(hours.round_raw_hours().as_decimal(), expected_raw_hours);
+        assert_eq!(crate::numbers::round_hours(hours.as_decimal()), expected_hours);
+        assert_eq!(crate::numbers::round_raw_hours(hours.as_decimal()), expected_raw_hours);
+    }
+
+    #[test]
+    fn test_too_large() {
+        assert_eq!(Hours::from_big_decimal(&BigDecimal::from_str("1e30").unwrap()), None);
+        assert_eq!(Hours::from_big_decimal(&BigDecimal::from_str("1e100000000").unwrap()), None);
+        assert_eq!(
+            Hours::from_big_decimal(&BigDecimal::from_str("1e-100000000").unwrap()),
+            Some(Hours::zero())
+        );
+        assert_eq!(Hours::checked_from_minutes(i64::MAX), None);
+        let largest = Hours::from_duration(Duration::milliseconds(i64::MAX));
+        assert_eq!(largest.round_to(0, RoundingMode::AwayFromZero), largest);
+        assert_eq!((-largest).round_to(0, RoundingMode::AwayFromZero), -largest);
+    }
+
+    #[rstest]
+    #[case(Hours::from_minutes(465), "07:45")]
+    #[case(Hours::from_minutes(2_400), "40:00")]
+    #[case(Hours::from_minutes(-90), "-01:30")]
+    #[case(Hours::from_duration(Duration::seconds(119)), "00:01")]
+    fn test_display(#[case] hours: Hours, #[case] expected: &str) {
+        assert_eq!(hours.to_string(), expected);
+        assert_eq!(hours.as_hh_mm(), expected);
+    }
+
+    #[rstest]
+    #[case("07:45", Ok(Hours::from_minutes(465)))]
+    #[case("7:45", Ok(Hours::from_minutes(465)))]
+    #[case("-01:30", Ok(Hours::from_minutes(-90)))]
+    #[case("40:00", Ok(Hours::from_minutes(2_400)))]
+    #[case("7.75", Ok(Hours::from_minutes(465)))]
+    #[case("-0.5", Ok(Hours::from_minutes(-30)))]
+    #[case("7:60", Err(HoursParseError("7:60".to_string())))]
+    #[case("7:5", Err(HoursParseError("7:5".to_string())))]
+    #[case("seven", Err(HoursParseError("seven".to_string())))]
+    #[case("1e30", Err(HoursParseError("1e30".to_string())))]
+    #[case("1e100000000", Err(HoursParseError("1e100000000".to_string())))]
+    #[case("7.", Err(HoursParseError("7.".to_string())))]
+    #[case(".5", Err(HoursParseError(".5".to_string())))]
+    #[case("+7.75", Ok(Hours::from_minutes(465)))]
+    #[case("99999999999999999999.5", Err(HoursParseError("99999999999999999999.5".to_string())))]
+    #[case("-1e30", Err(HoursParseError("-1e30".to_string())))]
+    #[case("99999999999999999:00", Err(HoursParseError("99999999999999999:00".to_string())))]
+    #[case("999999999999999999999:00", Err(HoursParseError("999999999999999999999:00".to_string())))]
+    #[case("2562047788015:00", Ok(Hours::from_minutes(153_722_867_280_900)))]
+    #[case("2562047788016:00", Err(HoursParseError("2562047788016:00".to_string())))]
+    fn test_from_str(#[case] text: &str, #[case] expected: Result<Hours, HoursParseError>) {
+        assert_eq!(text.parse::<Hours>(), expected);
+    }
+}
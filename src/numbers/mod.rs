@@ -1,2 +1,14 @@
 pub mod numbers;
 pub use numbers::*;
+pub mod rounding;
+pub use rounding::*;
+pub mod money;
+pub use money::*;
+pub mod allocation;
+pub use allocation::*;
+pub mod hours;
+pub use hours::*;
+pub mod percent;
+pub use percent::*;
+pub mod number_format;
+pub use number_format::*;
@@ -0,0 +1,354 @@
+use crate::numbers::{allocate, AllocationError, RoundWithMode, RoundingMode};
+use bigdecimal::{BigDecimal, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// An ISO 4217 currency with the number of minor units (decimal places) it is quoted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    code: [u8; 3],
+    minor_units: u32,
+}
+
+impl Currency {
+    pub const USD: Currency = Currency::known(b"USD", 2);
+    pub const CAD: Currency = Currency::known(b"CAD", 2);
+    pub const MXN: Currency = Currency::known(b"MXN", 2);
+    pub const EUR: Currency = Currency::known(b"EUR", 2);
+    pub const GBP: Currency = Currency::known(b"GBP", 2);
+    pub const CHF: Currency = Currency::known(b"CHF", 2);
+    pub const AUD: Currency = Currency::known(b"AUD", 2);
+    pub const NZD: Currency = Currency::known(b"NZD", 2);
+    pub const CNY: Currency = Currency::known(b"CNY", 2);
+    pub const INR: Currency = Currency::known(b"INR", 2);
+    pub const JPY: Currency = Currency::known(b"JPY", 0);
+    pub const KRW: Currency = Currency::known(b"KRW", 0);
+    pub const BHD: Currency = Currency::known(b"BHD", 3);
+    pub const KWD: Currency = Currency::known(b"KWD", 3);
+
+    const KNOWN: [Currency; 14] = [
+        Currency::USD,
+        Currency::CAD,
+        Currency::MXN,
+        Currency::EUR,
+        Currency::GBP,
+        Currency::CHF,
+        Currency::AUD,
+        Currency::NZD,
+        Currency::CNY,
+        Currency::INR,
+        Currency::JPY,
+        Currency::KRW,
+        Currency::BHD,
+        Currency::KWD,
+    ];
+
+    const fn known(code: &[u8; 3], minor_units: u32) -> Self {
+        Self {
+            code: *code,
+            minor_units,
+        }
+    }
+
+    /// Get one of the built-in currencies by its code.
+    pub fn of(code: &str) -> Result<Self, MoneyError> {
+        Self::KNOWN
+            .iter()
+            .find(|currency| currency.code() == code)
+            .copied()
+            .ok_or_else(|| MoneyError::UnknownCurrency(code.to_string()))
+    }
+
+    /// Create a currency that is not built in. The code must be three uppercase letters.
+    pub fn with_minor_units(code: &str, minor_units: u32) -> Result<Self, MoneyError> {
+        match code.as_bytes() {
+            [a, b, c] if [a, b, c].iter().all(|byte| byte.is_ascii_uppercase()) => Ok(Self {
+                code: [*a, *b, *c],
+                minor_units,
+            }),
+            _ => Err(MoneyError::UnknownCurrency(code.to_string())),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.code).unwrap()
+    }
+
+    pub fn minor_units(&self) -> u32 {
+        self.minor_units
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    UnknownCurrency(String),
+    CurrencyMismatch(Currency, Currency),
+    InvalidAmount(String),
+    DivisionByZero,
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::UnknownCurrency(code) => write!(f, "'{}' is not a known currency", code),
+            MoneyError::CurrencyMismatch(left, right) => {
+                write!(f, "cannot combine amounts in {} and {}", left, right)
+            }
+            MoneyError::InvalidAmount(text) => write!(f, "'{}' is not a valid amount of money", text),
+            MoneyError::DivisionByZero => write!(f, "cannot divide an amount of money by zero"),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+/// An exact amount of money in a currency.
+///
+/// The amount keeps whatever precision it is given until it is rounded, so intermediate
+/// results such as hourly rates can carry more decimals than the currency's minor units.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Money {
+    amount: BigDecimal,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn of(amount: BigDecimal, currency: Currency) -> Self {
+        Self { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Self::of(BigDecimal::zero(), currency)
+    }
+
+    /// Create an amount from a count of minor units, e.g. cents.
+    pub fn from_minor_units(minor_units: i64, currency: Currency) -> Self {
+        Self::of(
+            BigDecimal::new(minor_units.into(), currency.minor_units as i64),
+            currency,
+        )
+    }
+
+    pub fn amount(&self) -> &BigDecimal {
+        &self.amount
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.amount < BigDecimal::zero()
+    }
+
+    pub fn negate(&self) -> Self {
+        Self::of(-&self.amount, self.currency)
+    }
+
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.check_currency(other)?;
+        Ok(Self::of(&self.amount + &other.amount, self.currency))
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.check_currency(other)?;
+        Ok(Self::of(&self.amount - &other.amount, self.currency))
+    }
+
+    /// Multiply by a factor such as hours or a tax rate. The result is not rounded.
+    pub fn multiply(&self, factor: &BigDecimal) -> Money {
+        Self::of(&self.amount * factor, self.currency)
+    }
+
+    /// Divide by a divisor such as hours worked. The result is not rounded.
+    pub fn checked_div(&self, divisor: &BigDecimal) -> Result<Money, MoneyError> {
+        if divisor.is_zero() {
+            return Err(MoneyError::DivisionByZero);
+        }
+        Ok(Self::of(&self.amount / divisor, self.currency))
+    }
+
+    /// Compare two amounts in the same currency.
+    pub fn checked_cmp(&self, other: &Money) -> Result<Ordering, MoneyError> {
+        self.check_currency(other)?;
+        Ok(self.amount.cmp(&other.amount))
+    }
+
+    /// Add up amounts, all of which must be in the currency.
+    pub fn checked_sum<'a, I: IntoIterator<Item = &'a Money>>(
+        amounts: I,
+        currency: Currency,
+    ) -> Result<Money, MoneyError> {
+        amounts
+            .into_iter()
+            .try_fold(Self::zero(currency), |total, amount| total.checked_add(amount))
+    }
+
+    /// Round to the currency's minor units.
+    pub fn round(&self, mode: RoundingMode) -> Money {
+        self.round_to(self.currency.minor_units as usize, mode)
+    }
+
+    pub fn round_to(&self, number_decimals: usize, mode: RoundingMode) -> Money {
+        Self::of(self.amount.round_with_mode(number_decimals, mode), self.currency)
+    }
+
+    /// Split the amount into parts proportional to the weights, in the currency's minor
+    /// units, that add up exactly to the amount. See `allocate`.
+    pub fn allocate(&self, weights: &[BigDecimal]) -> Result<Vec<Money>, AllocationError> {
+        let parts = allocate(&self.amount, weights, self.currency.minor_units as usize)?;
+        Ok(parts.into_iter().map(|part| Self::of(part, self.currency)).collect())
+    }
+
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch(self.currency, other.currency))
+        }
+    }
+}
+
+/// Writes the amount followed by the currency code, e.g. `12.50 USD`. The amount is shown
+/// with at least the currency's minor units and is never rounded.
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minor_units = self.currency.minor_units as i64;
+        let amount = if self.amount.fractional_digit_count() < minor_units {
+            self.amount.with_scale(minor_units)
+        } else {
+            self.amount.clone()
+        };
+        write!(f, "{} {}", amount, self.currency)
+    }
+}
+
+/// Parses `12.50 USD` or `USD 12.50`.
+impl FromStr for Money {
+    type Err = MoneyError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || MoneyError::InvalidAmount(text.to_string());
+        let (first, second) = text.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let second = second.trim();
+
+        let (amount, code) = if first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            (second, first)
+        } else {
+            (first, second)
+        };
+        let amount = BigDecimal::from_str(amount).map_err(|_| invalid())?;
+        Ok(Self::of(amount, Currency::of(code)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn usd(amount: &str) -> Money {
+        Money::of(BigDecimal::from_str(amount).unwrap(), Currency::USD)
+    }
+
+    #[test]
+    fn test_currency() {
+        assert_eq!(Currency::of("JPY"), Ok(Currency::JPY));
+        assert_eq!(Currency::JPY.minor_units(), 0);
+        assert_eq!(Currency::of("XYZ"), Err(MoneyError::UnknownCurrency("XYZ".to_string())));
+        assert_eq!(Currency::with_minor_units("XYZ", 1).unwrap().code(), "XYZ");
+        assert_eq!(
+            Currency::with_minor_units("usd", 2),
+            Err(MoneyError::UnknownCurrency("usd".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // 0.1 + 0.2 is exact, unlike f64
+        assert_eq!(usd("0.1").checked_add(&usd("0.2")), Ok(usd("0.3")));
+        assert_eq!(usd("10.00").checked_sub(&usd("12.50")), Ok(usd("-2.50")));
+        assert_eq!(usd("15.25").multiply(&BigDecimal::from(8)), usd("122.00"));
+        assert_eq!(usd("100").checked_div(&BigDecimal::from(8)), Ok(usd("12.5")));
+        assert_eq!(
+            usd("100").checked_div(&BigDecimal::zero()),
+            Err(MoneyError::DivisionByZero)
+        );
+        assert_eq!(usd("1.5").negate(), usd("-1.5"));
+    }
+
+    #[test]
+    fn test_mixed_currencies() {
+        let euros = Money::from_minor_units(250, Currency::EUR);
+        let expected = Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR));
+        assert_eq!(usd("1").checked_add(&euros), expected);
+        assert_eq!(usd("1").checked_sub(&euros), expected);
+        assert_eq!(
+            usd("1").checked_cmp(&euros),
+            Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR))
+        );
+        assert_eq!(
+            Money::checked_sum(&[usd("1"), euros], Currency::USD),
+            Err(MoneyError::CurrencyMismatch(Currency::USD, Currency::EUR))
+        );
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        let amounts: Vec<Money> = (0..10).map(|_| usd("0.1")).collect();
+        assert_eq!(Money::checked_sum(&amounts, Currency::USD), Ok(usd("1")));
+        assert_eq!(Money::checked_sum(&[], Currency::USD), Ok(usd("0")));
+    }
+
+    #[test]
+    fn test_allocate() {
+        let weights = vec![BigDecimal::from(1); 3];
+        assert_eq!(usd("10").allocate(&weights), Ok(vec![usd("3.34"), usd("3.33"), usd("3.33")]));
+        assert_eq!(
+            usd("10.005").allocate(&weights),
+            Err(AllocationError::TooManyDecimals(BigDecimal::from_str("10.005").unwrap()))
+        );
+    }
+
+    #[rstest]
+    #[case(usd("2.345"), RoundingMode::HalfUp, "2.35 USD")]
+    #[case(usd("2.345"), RoundingMode::HalfEven, "2.34 USD")]
+    #[case(usd("-2.341"), RoundingMode::Floor, "-2.35 USD")]
+    #[case(Money::of(BigDecimal::from_str("1234.5").unwrap(), Currency::JPY), RoundingMode::HalfEven, "1234 JPY")]
+    #[case(Money::of(BigDecimal::from_str("1.2345").unwrap(), Currency::KWD), RoundingMode::HalfUp, "1.235 KWD")]
+    fn test_round(#[case] money: Money, #[case] mode: RoundingMode, #[case] expected: &str) {
+        assert_eq!(money.round(mode).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(usd("12.5"), "12.50 USD")]
+    #[case(usd("12.3456"), "12.3456 USD")]
+    #[case(usd("-3"), "-3.00 USD")]
+    #[case(Money::from_minor_units(1999, Currency::USD), "19.99 USD")]
+    #[case(Money::from_minor_units(500, Currency::JPY), "500 JPY")]
+    fn test_display(#[case] money: Money, #[case] expected: &str) {
+        assert_eq!(money.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("12.50 USD", Ok(usd("12.5")))]
+    #[case("USD -3.25", Ok(usd("-3.25")))]
+    #[case("500 JPY", Ok(Money::from_minor_units(500, Currency::JPY)))]
+    #[case("12.50", Err(MoneyError::InvalidAmount("12.50".to_string())))]
+    #[case("1x.50 USD", Err(MoneyError::InvalidAmount("1x.50 USD".to_string())))]
+    #[case("12.50 ABC", Err(MoneyError::UnknownCurrency("ABC".to_string())))]
+    fn test_from_str(#[case] text: &str, #[case] expected: Result<Money, MoneyError>) {
+        assert_eq!(text.parse::<Money>(), expected);
+    }
+}
@@ -0,0 +1,308 @@
+use crate::numbers::{Currency, Hours, Money, RoundWithMode, RoundingMode, MAX_F64_DECIMALS};
+use bigdecimal::{BigDecimal, Signed};
+use std::str::FromStr;
+
+/// The built-in locale profiles. They cover the conventions used in our reports and need no
+/// external locale data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// `1,234.50`, `$1,234.50`
+    #[default]
+    EnUs,
+    /// `1,234.50`, `£1,234.50`
+    EnGb,
+    /// `1.234,50`, `1.234,50 €`
+    DeDe,
+    /// `1 234,50`, `1 234,50 €`
+    FrFr,
+    /// `1'234.50`, `CHF 1'234.50`
+    DeCh,
+}
+
+/// How negative values are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NegativeStyle {
+    /// `-1,234.50`
+    #[default]
+    Minus,
+    /// `(1,234.50)`, as in accounting reports.
+    Parentheses,
+    /// `1,234.50-`
+    TrailingMinus,
+}
+
+/// Where a currency symbol goes relative to the number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SymbolPlacement {
+    /// `$1.00`
+    #[default]
+    Before,
+    /// `CHF 1.00`
+    BeforeWithSpace,
+    /// `1,00€`
+    After,
+    /// `1,00 €`
+    AfterWithSpace,
+}
+
+/// How to write numbers, amounts of money and hours for display.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NumberFormat {
+    grouping_separator: Option<char>,
+    decimal_separator: char,
+    negative_style: NegativeStyle,
+    symbol_placement: SymbolPlacement,
+    min_decimals: usize,
+    max_decimals: usize,
+    rounding_mode: RoundingMode,
+}
+
+impl NumberFormat {
+    /// Get the format for a locale, with between 0 and 2 decimals.
+    pub fn for_locale(locale: Locale) -> Self {
+        let (grouping_separator, decimal_separator, symbol_placement) = match locale {
+            Locale::EnUs | Locale::EnGb => (',', '.', SymbolPlacement::Before),
+            Locale::DeDe => ('.', ',', SymbolPlacement::AfterWithSpace),
+            Locale::FrFr => (' ', ',', SymbolPlacement::AfterWithSpace),
+            Locale::DeCh => ('\'', '.', SymbolPlacement::BeforeWithSpace),
+        };
+
+        Self {
+            grouping_separator: Some(grouping_separator),
+            decimal_separator,
+            negative_style: NegativeStyle::Minus,
+            symbol_placement,
+            min_decimals: 0,
+            max_decimals: 2,
+            rounding_mode: RoundingMode::HalfUp,
+        }
+    }
+
+    pub fn with_grouping_separator(mut self, grouping_separator: Option<char>) -> Self {
+        self.grouping_separator = grouping_separator;
+        self
+    }
+
+    pub fn with_decimal_separator(mut self, decimal_separator: char) -> Self {
+        self.decimal_separator = decimal_separator;
+        self
+    }
+
+    pub fn with_negative_style(mut self, negative_style: NegativeStyle) -> Self {
+        self.negative_style = negative_style;
+        self
+    }
+
+    pub fn with_symbol_placement(mut self, symbol_placement: SymbolPlacement) -> Self {
+        self.symbol_placement = symbol_placement;
+        self
+    }
+
+    /// Round to at most `max_decimals`, then drop trailing zeros down to `min_decimals`. Both
+    /// are limited to the most decimals rounding an `f64` can need.
+    pub fn with_decimals(mut self, min_decimals: usize, max_decimals: usize) -> Self {
+        self.max_decimals = max_decimals.min(MAX_F64_DECIMALS);
+        self.min_decimals = min_decimals.min(self.max_decimals);
+        self
+    }
+
+    /// Always write exactly `number_decimals` decimals.
+    pub fn with_fixed_decimals(self, number_decimals: usize) -> Self {
+        self.with_decimals(number_decimals, number_decimals)
+    }
+
+    pub fn with_rounding_mode(mut self, rounding_mode: RoundingMode) -> Self {
+        self.rounding_mode = rounding_mode;
+        self
+    }
+
+    /// Format a number. NaN and infinities are written as `NaN`, `∞` and `-∞`.
+    pub fn format(&self, value: f64) -> String {
+        if value.is_nan() {
+            return "NaN".to_string();
+        }
+        if value.is_infinite() {
+            return if value > 0.0 { "∞" } else { "-∞" }.to_string();
+        }
+
+        let decimal = BigDecimal::from_str(&value.to_string()).unwrap();
+        self.format_decimal(&decimal)
+    }
+
+    pub fn format_decimal(&self, value: &BigDecimal) -> String {
+        let (negative, number) = self.format_unsigned(value, self.min_decimals, self.max_decimals);
+        self.apply_sign(negative, number)
+    }
+
+    /// Format an amount of money with the currency's symbol and exactly its minor units.
+    pub fn format_money(&self, money: &Money) -> String {
+        let minor_units = money.currency().minor_units() as usize;
+        let (negative, number) = self.format_unsigned(money.amount(), minor_units, minor_units);
+        let symbol = currency_symbol(money.currency());
+
+        let text = match self.symbol_placement {
+            SymbolPlacement::Before => format!("{}{}", symbol, number),
+            SymbolPlacement::BeforeWithSpace => format!("{} {}", symbol, number),
+            SymbolPlacement::After => format!("{}{}", number, symbol),
+            SymbolPlacement::AfterWithSpace => format!("{} {}", number, symbol),
+        };
+        self.apply_sign(negative, text)
+    }
+
+    /// Format decimal hours followed by `h`, e.g. `37.5 h`.
+    pub fn format_hours(&self, hours: &Hours) -> String {
+        let hours = hours.as_big_decimal(self.max_decimals, self.rounding_mode);
+        format!("{} h", self.format_decimal(&hours))
+    }
+
+    /// Round the value and write its digits, returning whether it is still negative.
+    fn format_unsigned(&self, value: &BigDecimal, min_decimals: usize, max_decimals: usize) -> (bool, String) {
+        let rounded = value.round_with_mode(max_decimals, self.rounding_mode);
+        let negative = rounded.is_negative();
+
+        // Rounding does not always leave exactly `max_decimals` decimals, so split the digits
+        // at the scale they actually have.
+        let (digits, scale) = rounded.abs().as_bigint_and_exponent();
+        let mut digits = digits.to_string();
+        let scale = match usize::try_from(scale) {
+            Ok(scale) => scale,
+            Err(_) => {
+                digits.push_str(&"0".repeat(scale.unsigned_abs() as usize));
+                0
+            }
+        };
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+
+        let mut fraction = fraction.to_string();
+        while fraction.len() > min_decimals && fraction.ends_with('0') {
+            fraction.pop();
+        }
+        while fraction.len() < min_decimals {
+            fraction.push('0');
+        }
+
+        let mut text = self.group(integer);
+        if !fraction.is_empty() {
+            text.push(self.decimal_separator);
+            text.push_str(&fraction);
+        }
+        (negative, text)
+    }
+
+    fn group(&self, integer: &str) -> String {
+        let Some(separator) = self.grouping_separator else {
+            return integer.to_string();
+        };
+
+        let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+        for (index, digit) in integer.chars().enumerate() {
+            if index > 0 && (integer.len() - index).is_multiple_of(3) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    fn apply_sign(&self, negative: bool, text: String) -> String {
+        if !negative {
+            return text;
+        }
+        match self.negative_style {
+            NegativeStyle::Minus => format!("-{}", text),
+            NegativeStyle::Parentheses => format!("({})", text),
+            NegativeStyle::TrailingMinus => format!("{}-", text),
+        }
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::for_locale(Locale::default())
+    }
+}
+
+fn currency_symbol(currency: Currency) -> &'static str {
+    match currency.code() {
+        "USD" | "CAD" | "MXN" | "AUD" | "NZD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" | "CNY" => "¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        "CHF" => "CHF",
+        "BHD" => "BHD",
+        "KWD" => "KWD",
+        _ => "¤",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn money(amount: &str, currency: Currency) -> Money {
+        Money::of(BigDecimal::from_str(amount).unwrap(), currency)
+    }
+
+    #[rstest]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_fixed_decimals(2), 1234.5, "1,234.50")]
+    #[case(NumberFormat::for_locale(Locale::DeDe).with_fixed_decimals(2), 1234.5, "1.234,50")]
+    #[case(NumberFormat::for_locale(Locale::FrFr).with_fixed_decimals(2), 1234.5, "1 234,50")]
+    #[case(NumberFormat::for_locale(Locale::DeCh).with_fixed_decimals(2), 1234567.5, "1'234'567.50")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), 1234.5, "1,234.5")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), 1234.0, "1,234")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), 0.005, "0.01")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), -0.004, "0")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), 999.999, "1,000")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_decimals(1, 3), 2.0, "2.0")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_grouping_separator(None), 1234567.0, "1234567")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_negative_style(NegativeStyle::Parentheses).with_fixed_decimals(2), -1234.5, "(1,234.50)")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_negative_style(NegativeStyle::TrailingMinus), -12.0, "12-")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_rounding_mode(RoundingMode::HalfEven), 0.125, "0.12")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_decimals(0, usize::MAX), 1234.5, "1,234.5")]
+    #[case(NumberFormat::for_locale(Locale::EnUs).with_decimals(3, usize::MAX), 1e20, "100,000,000,000,000,000,000.000")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), f64::NAN, "NaN")]
+    #[case(NumberFormat::for_locale(Locale::EnUs), f64::NEG_INFINITY, "-∞")]
+    fn test_format(#[case] format: NumberFormat, #[case] value: f64, #[case] expected: &str) {
+        assert_eq!(format.format(value), expected);
+    }
+
+    #[rstest]
+    #[case(Locale::EnUs, money("1234.5", Currency::USD), "$1,234.50")]
+    #[case(Locale::EnUs, money("-1234.5", Currency::USD), "-$1,234.50")]
+    #[case(Locale::DeDe, money("1234.5", Currency::EUR), "1.234,50 €")]
+    #[case(Locale::FrFr, money("-0.5", Currency::EUR), "-0,50 €")]
+    #[case(Locale::EnGb, money("99.999", Currency::GBP), "£100.00")]
+    #[case(Locale::DeCh, money("1234.5", Currency::CHF), "CHF 1'234.50")]
+    #[case(Locale::EnUs, money("1234.5", Currency::JPY), "¥1,235")]
+    fn test_format_money(#[case] locale: Locale, #[case] money: Money, #[case] expected: &str) {
+        assert_eq!(NumberFormat::for_locale(locale).format_money(&money), expected);
+    }
+
+    #[test]
+    fn test_format_decimal_with_positive_exponent() {
+        let format = NumberFormat::for_locale(Locale::EnUs).with_fixed_decimals(2);
+        assert_eq!(
+            format.format_decimal(&BigDecimal::from_str("12E+3").unwrap()),
+            "12,000.00"
+        );
+    }
+
+    #[test]
+    fn test_format_money_accounting() {
+        let format = NumberFormat::for_locale(Locale::EnUs).with_negative_style(NegativeStyle::Parentheses);
+        assert_eq!(format.format_money(&money("-1234.5", Currency::USD)), "($1,234.50)");
+    }
+
+    #[rstest]
+    #[case(Locale::EnUs, Hours::from_minutes(2_250), "37.5 h")]
+    #[case(Locale::DeDe, Hours::from_minutes(2_250), "37,5 h")]
+    #[case(Locale::EnUs, Hours::from_minutes(20), "0.33 h")]
+    #[case(Locale::EnUs, Hours::from_minutes(-90), "-1.5 h")]
+    fn test_format_hours(#[case] locale: Locale, #[case] hours: Hours, #[case] expected: &str) {
+        assert_eq!(NumberFormat::for_locale(locale).format_hours(&hours), expected);
+    }
+}
@@ -1,61 +1,60 @@
 const ROUND_MULTIPLIERS: [f64; 6] = [1.0, 10.0, 100.0, 1000.0, 10000.0, 100000.0];
 
 const CURRENCY_ROUND_PRECISION: usize = 4;
-const HOURS_ROUND_PRECISION: usize = 2;
-const RAW_HOURS_ROUND_PRECISION: usize = 4;
-const ROUND_PERCENT_PRECISION: usize = 4;
+pub(crate) const HOURS_ROUND_PRECISION: usize = 2;
+pub(crate) const RAW_HOURS_ROUND_PRECISION: usize = 4;
+pub(crate) const ROUND_PERCENT_PRECISION: usize = 4;
 
+/// Beyond this many decimals every finite `f64` is already as precise as it can be.
+pub(crate) const MAX_F64_DECIMALS: usize = 400;
+
+/// 2^53: from here on every `f64` is a whole number, so there is nothing left to round.
+const F64_INTEGER_LIMIT: f64 = 9_007_199_254_740_992.0;
+
+/// Round to the nearest integer with halves going away from zero, like `RoundingMode::HalfUp`
+/// but applied to the binary value, so 2.675 * 100.0 (267.49999...) rounds down. Values outside
+/// the `i32` range saturate and NaN becomes 0; use `checked_round` to catch those.
 pub fn round(value: f64) -> i32 {
-    if value >= 0.0 {
-        if value - (value as i32 as f64) >= 0.5 {
-            value as i32 + 1
-        } else {
-            value as i32
-        }
-    } else {
-        let temp_value = -value;
-        let mut result = if temp_value - (temp_value as i32 as f64) >= 0.5 {
-            temp_value as i32 + 1
-        } else {
-            temp_value as i32
-        };
-        result = -result;
-        result
-    }
+    // f64::round also rounds halves away from zero, and `as` saturates instead of overflowing.
+    value.round() as i32
 }
 
+/// The `i64` version of `round`. Values outside the `i64` range saturate and NaN becomes 0;
+/// use `checked_round_long` to catch those.
 pub fn round_long(value: f64) -> i64 {
-    if value >= 0.0 {
-        if value - (value as i64 as f64) >= 0.5 {
-            value as i64 + 1
-        } else {
-            value as i64
-        }
-    } else {
-        let temp_value = -value;
-        let mut result = if temp_value - (temp_value as i64 as f64) >= 0.5 {
-            temp_value as i64 + 1
-        } else {
-            temp_value as i64
-        };
-        result = -result;
-        result
-    }
+    value.round() as i64
 }
 
+/// Round half away from zero after nudging the value away from zero by `0.01 / multiplier`
+/// of a unit in the last place, so values just below a half, such as the binary value of
+/// 1.005, round up. Results smaller than one unit in the last place become 0.0. Use
+/// `round_with_mode` to round with an exact rule.
+///
+/// Any number of decimals is allowed. Once the value has no digits beyond `number_decimals`
+/// that an `f64` can hold, it is returned unchanged.
 pub fn round_with_decimals(value: f64, number_decimals: usize) -> f64 {
     if number_decimals == 0 {
         return value.round(); // Java’s Math.rint → Rust’s round()
     }
 
-    let multiplier = ROUND_MULTIPLIERS[number_decimals];
+    let multiplier = match ROUND_MULTIPLIERS.get(number_decimals) {
+        Some(multiplier) => *multiplier,
+        None => {
+            let multiplier = 10f64.powi(number_decimals.min(MAX_F64_DECIMALS) as i32);
+            let scaled = value * multiplier;
+            if !scaled.is_finite() || scaled.abs() >= F64_INTEGER_LIMIT {
+                return value;
+            }
+            multiplier
+        }
+    };
     let signum = value.signum();
 
     // Hokie trick for floating point edge cases
     let rounded = ((value * multiplier) + signum * 0.01 / multiplier).round() / multiplier;
 
     // epsilon tolerance
-    let epsilon = 10f64.powi(-(number_decimals as i32));
+    let epsilon = 1.0 / multiplier;
     if rounded <= (0.0 - epsilon) || rounded >= (0.0 + epsilon) {
         rounded
     } else {
@@ -79,13 +78,58 @@ pub fn round_raw_hours(value: f64) -> f64 {
     round_with_decimals(value, RAW_HOURS_ROUND_PRECISION)
 }
 
+/// Drop the fractional part. Values outside the `i32` range saturate and NaN becomes 0; use
+/// `checked_truncate` to catch those.
 pub fn truncate(value: f64) -> i32 {
     value as i32
 }
 
+/// Like `round`, but `None` for NaN, infinities and results outside the `i32` range.
+pub fn checked_round(value: f64) -> Option<i32> {
+    let rounded = value.round();
+    if rounded >= i32::MIN as f64 && rounded <= i32::MAX as f64 {
+        Some(rounded as i32)
+    } else {
+        None
+    }
+}
+
+/// Like `round_long`, but `None` for NaN, infinities and results outside the `i64` range.
+pub fn checked_round_long(value: f64) -> Option<i64> {
+    // i64::MAX as f64 rounds up to 2^63, which is just past the range.
+    let rounded = value.round();
+    if rounded >= i64::MIN as f64 && rounded < i64::MAX as f64 {
+        Some(rounded as i64)
+    } else {
+        None
+    }
+}
+
+/// Like `truncate`, but `None` for NaN, infinities and results outside the `i32` range.
+pub fn checked_truncate(value: f64) -> Option<i32> {
+    let truncated = value.trunc();
+    if truncated >= i32::MIN as f64 && truncated <= i32::MAX as f64 {
+        Some(truncated as i32)
+    } else {
+        None
+    }
+}
+
+/// Like `round_with_decimals`, but `None` for NaN and infinities, which cannot be rounded.
+pub fn checked_round_with_decimals(value: f64, number_decimals: usize) -> Option<f64> {
+    if value.is_finite() {
+        Some(round_with_decimals(value, number_decimals))
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::numbers::{round, round_currency, round_hours, round_long, round_percent, round_raw_hours, round_with_decimals, truncate};
+    use crate::numbers::{
+        checked_round, checked_round_long, checked_round_with_decimals, checked_truncate, round, round_currency, round_hours, round_long, round_percent, round_raw_hours, round_with_decimals, round_with_mode,
+        truncate, RoundingMode,
+    };
     use rstest::rstest;
 
     #[rstest]
@@ -201,6 +245,52 @@ mod tests {
         assert_eq!(round_raw_hours(value), expected_result);
     }
 
+    #[rstest]
+    #[case(-0.49, 0)]
+    #[case(-0.5, -1)]
+    #[case(-1.5, -2)]
+    #[case(-2.5, -3)]
+    #[case(2.5, 3)]
+    #[case(0.49999999999999994, 0)]
+    fn test_round_negative_and_halves(#[case] value: f64, #[case] expected_result: i32) {
+        assert_eq!(round(value), expected_result);
+        assert_eq!(round_long(value), expected_result as i64);
+    }
+
+    #[rstest]
+    #[case(2.5, 0, 3.0)]
+    #[case(-2.5, 0, -3.0)]
+    #[case(1.005, 2, 1.01)]
+    #[case(2.675, 2, 2.68)]
+    #[case(1.0049, 2, 1.0)]
+    #[case(1.0049995, 2, 1.01)]
+    #[case(-1.005, 2, -1.01)]
+    #[case(-0.125, 2, -0.13)]
+    #[case(0.00004, 4, 0.0)]
+    #[case(-0.00004, 4, 0.0)]
+    #[case(0.00005, 4, 0.0001)]
+    #[case(12345.678915, 5, 12345.67892)]
+    fn test_round_with_decimals_edge_cases(
+        #[case] value: f64,
+        #[case] number_decimals: usize,
+        #[case] expected_result: f64,
+    ) {
+        assert_eq!(round_with_decimals(value, number_decimals), expected_result);
+    }
+
+    #[rstest]
+    #[case(1.0049995, 1.01, 1.0)]
+    #[case(0.125, 0.13, 0.13)]
+    #[case(2.675, 2.68, 2.68)]
+    fn test_round_with_decimals_compared_to_half_up(
+        #[case] value: f64,
+        #[case] expected_preset: f64,
+        #[case] expected_half_up: f64,
+    ) {
+        assert_eq!(round_hours(value), expected_preset);
+        assert_eq!(round_with_mode(value, 2, RoundingMode::HalfUp), expected_half_up);
+    }
+
     #[rstest]
     #[case(0.0, 0)]
     #[case(0.1, 0)]
@@ -216,4 +306,72 @@ mod tests {
     fn test_truncate(#[case] value: f64, #[case] expected_result: i32) {
         assert_eq!(truncate(value), expected_result);
     }
+
+    #[rstest]
+    #[case(1.23456789, 6, 1.234568)]
+    #[case(1.23456789, 7, 1.2345679)]
+    #[case(-1.23456789, 6, -1.234568)]
+    #[case(0.0000004, 6, 0.0)]
+    #[case(0.1 + 0.2, 15, 0.3)]
+    #[case(0.1 + 0.2, 17, 0.1 + 0.2)]
+    #[case(123456789.12345679, 12, 123456789.12345679)]
+    #[case(1e-300, 20, 0.0)]
+    #[case(1.5, 1_000, 1.5)]
+    #[case(1.5, usize::MAX, 1.5)]
+    fn test_round_with_many_decimals(
+        #[case] value: f64,
+        #[case] number_decimals: usize,
+        #[case] expected_result: f64,
+    ) {
+        assert_eq!(round_with_decimals(value, number_decimals), expected_result);
+    }
+
+    #[rstest]
+    #[case(f64::NAN, None)]
+    #[case(f64::INFINITY, None)]
+    #[case(f64::NEG_INFINITY, None)]
+    #[case(2_147_483_647.4, Some(i32::MAX))]
+    #[case(2_147_483_647.5, None)]
+    #[case(-2_147_483_648.4, Some(i32::MIN))]
+    #[case(-2_147_483_648.5, None)]
+    #[case(-1.5, Some(-2))]
+    fn test_checked_round(#[case] value: f64, #[case] expected_result: Option<i32>) {
+        assert_eq!(checked_round(value), expected_result);
+    }
+
+    #[rstest]
+    #[case(f64::NAN, None)]
+    #[case(9.2e18, Some(9_200_000_000_000_000_000))]
+    #[case(9.3e18, None)]
+    #[case(i64::MAX as f64, None)]
+    #[case(i64::MIN as f64, Some(i64::MIN))]
+    #[case(2.5, Some(3))]
+    fn test_checked_round_long(#[case] value: f64, #[case] expected_result: Option<i64>) {
+        assert_eq!(checked_round_long(value), expected_result);
+    }
+
+    #[rstest]
+    #[case(f64::NAN, None)]
+    #[case(2_147_483_647.9, Some(i32::MAX))]
+    #[case(2_147_483_648.0, None)]
+    #[case(-2_147_483_648.9, Some(i32::MIN))]
+    #[case(-1.9, Some(-1))]
+    fn test_checked_truncate(#[case] value: f64, #[case] expected_result: Option<i32>) {
+        assert_eq!(checked_truncate(value), expected_result);
+    }
+
+    #[test]
+    fn test_checked_round_with_decimals() {
+        assert_eq!(checked_round_with_decimals(0.505, 2), Some(0.51));
+        assert_eq!(checked_round_with_decimals(1.0, 10), Some(1.0));
+        assert_eq!(checked_round_with_decimals(f64::NAN, 2), None);
+        assert_eq!(checked_round_with_decimals(f64::INFINITY, 2), None);
+    }
+
+    #[test]
+    fn test_unchecked_saturates() {
+        assert_eq!(round(1e20), i32::MAX);
+        assert_eq!(round_long(-1e30), i64::MIN);
+        assert_eq!(truncate(f64::NAN), 0);
+    }
 }
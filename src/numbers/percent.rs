@@ -0,0 +1,292 @@
+use crate::numbers::{Money, RoundWithMode, RoundingMode, ROUND_PERCENT_PRECISION};
+use bigdecimal::{BigDecimal, Signed, ToPrimitive};
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// A percentage, stored as an exact decimal ratio so that 5% is 0.05.
+///
+/// Use the constructor that matches the number you have: `from_ratio(0.05)`,
+/// `from_percent(5.0)` and `from_basis_points(500.0)` are all 5%. The `f64` constructors
+/// take the shortest decimal that prints as the number, so `from_percent(29.0)` is exactly
+/// 29%, and return `None` for NaN and infinities.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Percent {
+    ratio: BigDecimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PercentParseError(pub String);
+
+impl fmt::Display for PercentParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a valid percentage", self.0)
+    }
+}
+
+impl std::error::Error for PercentParseError {}
+
+impl Percent {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ratio(ratio: f64) -> Option<Self> {
+        decimal_of(ratio).map(Self::from_big_decimal_ratio)
+    }
+
+    pub fn from_percent(percent: f64) -> Option<Self> {
+        decimal_of(percent).map(|percent| Self::from_big_decimal_percent(&percent))
+    }
+
+    /// A basis point is a hundredth of a percent, so 125 basis points is 1.25%.
+    pub fn from_basis_points(basis_points: f64) -> Option<Self> {
+        decimal_of(basis_points).map(|basis_points| Self::from_big_decimal_ratio(basis_points * ten_to_minus(4)))
+    }
+
+    pub fn from_big_decimal_ratio(ratio: BigDecimal) -> Self {
+        Self { ratio }
+    }
+
+    pub fn from_big_decimal_percent(percent: &BigDecimal) -> Self {
+        Self::from_big_decimal_ratio(percent * ten_to_minus(2))
+    }
+
+    pub fn as_ratio(&self) -> f64 {
+        to_f64(&self.ratio)
+    }
+
+    pub fn as_percent(&self) -> f64 {
+        to_f64(&self.as_big_decimal_percent())
+    }
+
+    pub fn as_basis_points(&self) -> f64 {
+        to_f64(&(&self.ratio * BigDecimal::from(10_000)))
+    }
+
+    pub fn as_big_decimal_ratio(&self) -> &BigDecimal {
+        &self.ratio
+    }
+
+    pub fn as_big_decimal_percent(&self) -> BigDecimal {
+        &self.ratio * BigDecimal::from(100)
+    }
+
+    pub fn abs(&self) -> Percent {
+        Self::from_big_decimal_ratio(self.ratio.abs())
+    }
+
+    /// Round to the nearest whole basis point, with halves going away from zero.
+    pub fn round_to_basis_points(&self) -> Percent {
+        Self::from_big_decimal_ratio(self.ratio.round_with_mode(4, RoundingMode::HalfUp))
+    }
+
+    /// Get this percentage of the amount, e.g. 15% of 200.0 is 30.0.
+    pub fn apply_to(&self, amount: f64) -> f64 {
+        amount * self.as_ratio()
+    }
+
+    /// Get this percentage of the amount of money. The result is exact and not rounded.
+    pub fn apply_to_money(&self, amount: &Money) -> Money {
+        amount.multiply(&self.ratio)
+    }
+
+    /// Format the percent value with up to `number_decimals` decimals, dropping trailing
+    /// zeros, e.g. `12.5%`. When `signed` is set, positive values get a `+`.
+    pub fn format(&self, number_decimals: usize, signed: bool) -> String {
+        let percent = self
+            .as_big_decimal_percent()
+            .round_with_mode(number_decimals, RoundingMode::HalfUp);
+        let mut text = percent.to_plain_string();
+        if text.contains('.') {
+            text = text.trim_end_matches('0').trim_end_matches('.').to_string();
+        }
+
+        let sign = if signed && percent.is_positive() { "+" } else { "" };
+        format!("{}{}%", sign, text)
+    }
+}
+
+/// Get 10 to the power of `-exponent`, e.g. 0.01 for 2.
+fn ten_to_minus(exponent: i64) -> BigDecimal {
+    BigDecimal::new(1.into(), exponent)
+}
+
+fn decimal_of(value: f64) -> Option<BigDecimal> {
+    if value.is_finite() {
+        BigDecimal::from_str(&value.to_string()).ok()
+    } else {
+        None
+    }
+}
+
+fn to_f64(value: &BigDecimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+impl Add for Percent {
+    type Output = Percent;
+
+    fn add(self, other: Percent) -> Percent {
+        Percent::from_big_decimal_ratio(self.ratio + other.ratio)
+    }
+}
+
+impl Sub for Percent {
+    type Output = Percent;
+
+    fn sub(self, other: Percent) -> Percent {
+        Percent::from_big_decimal_ratio(self.ratio - other.ratio)
+    }
+}
+
+impl Neg for Percent {
+    type Output = Percent;
+
+    fn neg(self) -> Percent {
+        Percent::from_big_decimal_ratio(-self.ratio)
+    }
+}
+
+/// Writes the percent with a sign and up to 4 decimals, the precision of `round_percent`,
+/// e.g. `+12.5%` or `-3.3333%`.
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(ROUND_PERCENT_PRECISION, true))
+    }
+}
+
+/// Parses a percent value followed by `%`, such as `+12.5%`, `-3 %` or `0.25%`.
+impl FromStr for Percent {
+    type Err = PercentParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || PercentParseError(text.to_string());
+        let number = text.trim().strip_suffix('%').ok_or_else(invalid)?.trim_end();
+        let number = number.strip_prefix('+').unwrap_or(number);
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit() || b == b'.' || b == b'-') {
+            return Err(invalid());
+        }
+        BigDecimal::from_str(number)
+            .map(|percent| Percent::from_big_decimal_percent(&percent))
+            .map_err(|_| invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numbers::Currency;
+    use rstest::rstest;
+
+    fn percent(percent: f64) -> Percent {
+        Percent::from_percent(percent).unwrap()
+    }
+
+    #[test]
+    fn test_constructors_agree() {
+        let five = percent(5.0);
+        assert_eq!(Percent::from_ratio(0.05), Some(five.clone()));
+        assert_eq!(Percent::from_basis_points(500.0), Some(five.clone()));
+        assert_eq!(Percent::from_big_decimal_percent(&BigDecimal::from(5)), five);
+        assert_eq!(five.as_ratio(), 0.05);
+        assert_eq!(five.as_percent(), 5.0);
+        assert_eq!(five.as_basis_points(), 500.0);
+    }
+
+    #[rstest]
+    #[case(29.0)]
+    #[case(12.345)]
+    #[case(-0.07)]
+    #[case(0.1)]
+    fn test_round_trip(#[case] value: f64) {
+        assert_eq!(percent(value).as_percent(), value);
+        assert_eq!(Percent::from_ratio(value / 100.0).unwrap().as_ratio(), value / 100.0);
+    }
+
+    #[rstest]
+    #[case(f64::NAN)]
+    #[case(f64::INFINITY)]
+    #[case(f64::NEG_INFINITY)]
+    fn test_not_finite(#[case] value: f64) {
+        assert_eq!(Percent::from_ratio(value), None);
+        assert_eq!(Percent::from_percent(value), None);
+        assert_eq!(Percent::from_basis_points(value), None);
+    }
+
+    #[test]
+    fn test_apply_to() {
+        assert_eq!(percent(15.0).apply_to(200.0), 30.0);
+        let money = Money::from_minor_units(2_000, Currency::USD);
+        assert_eq!(
+            Percent::from_basis_points(125.0).unwrap().apply_to_money(&money),
+            Money::from_minor_units(25, Currency::USD)
+        );
+        assert_eq!(
+            percent(0.5)
+                .apply_to_money(&Money::from_minor_units(1, Currency::USD))
+                .amount(),
+            &BigDecimal::from_str("0.00005").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_round_to_basis_points() {
+        assert_eq!(
+            Percent::from_ratio(0.123456)
+                .unwrap()
+                .round_to_basis_points()
+                .as_basis_points(),
+            1_235.0
+        );
+        assert_eq!(
+            Percent::from_ratio(-0.00005)
+                .unwrap()
+                .round_to_basis_points()
+                .as_basis_points(),
+            -1.0
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(percent(0.1) + percent(0.2), percent(0.3));
+        assert_eq!(percent(5.0) - percent(7.5), -percent(2.5));
+        assert_eq!(percent(-2.5).abs(), percent(2.5));
+        assert!(percent(29.0) < percent(29.01));
+    }
+
+    #[rstest]
+    #[case(percent(12.5), "+12.5%")]
+    #[case(percent(-3.333333), "-3.3333%")]
+    #[case(percent(100.0), "+100%")]
+    #[case(Percent::zero(), "0%")]
+    #[case(percent(-0.00001), "0%")]
+    fn test_display(#[case] percent: Percent, #[case] expected: &str) {
+        assert_eq!(percent.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(percent(12.5), 0, false, "13%")]
+    #[case(percent(12.345), 2, false, "12.35%")]
+    #[case(percent(12.0), 2, true, "+12%")]
+    fn test_format(
+        #[case] percent: Percent,
+        #[case] number_decimals: usize,
+        #[case] signed: bool,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(percent.format(number_decimals, signed), expected);
+    }
+
+    #[rstest]
+    #[case("+12.5%", Ok(percent(12.5)))]
+    #[case("-3 %", Ok(percent(-3.0)))]
+    #[case("0.25%", Ok(percent(0.25)))]
+    #[case("12.5", Err(PercentParseError("12.5".to_string())))]
+    #[case("%", Err(PercentParseError("%".to_string())))]
+    #[case("inf%", Err(PercentParseError("inf%".to_string())))]
+    fn test_from_str(#[case] text: &str, #[case] expected: Result<Percent, PercentParseError>) {
+        assert_eq!(text.parse::<Percent>(), expected);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::numbers::MAX_F64_DECIMALS;
+use bigdecimal::BigDecimal;
+use num_traits::ToPrimitive;
+use std::str::FromStr;
+
+/// How to round a value that falls between two representable results.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest value, with halves going away from zero: 2.5 → 3, -2.5 → -3.
+    #[default]
+    HalfUp,
+    /// Round to the nearest value, with halves going toward zero: 2.5 → 2, -2.5 → -2.
+    HalfDown,
+    /// Round to the nearest value, with halves going to the even neighbor: 2.5 → 2, 3.5 → 4.
+    HalfEven,
+    /// Round toward positive infinity: 2.1 → 3, -2.9 → -2.
+    Ceiling,
+    /// Round toward negative infinity: 2.9 → 2, -2.1 → -3.
+    Floor,
+    /// Drop the extra digits: 2.9 → 2, -2.9 → -2.
+    TowardZero,
+    /// Round up in magnitude: 2.1 → 3, -2.1 → -3.
+    AwayFromZero,
+}
+
+impl From<RoundingMode> for bigdecimal::RoundingMode {
+    fn from(mode: RoundingMode) -> Self {
+        match mode {
+            RoundingMode::HalfUp => bigdecimal::RoundingMode::HalfUp,
+            RoundingMode::HalfDown => bigdecimal::RoundingMode::HalfDown,
+            RoundingMode::HalfEven => bigdecimal::RoundingMode::HalfEven,
+            RoundingMode::Ceiling => bigdecimal::RoundingMode::Ceiling,
+            RoundingMode::Floor => bigdecimal::RoundingMode::Floor,
+            RoundingMode::TowardZero => bigdecimal::RoundingMode::Down,
+            RoundingMode::AwayFromZero => bigdecimal::RoundingMode::Up,
+        }
+    }
+}
+
+/// A number that can be rounded to a number of decimal places with a `RoundingMode`.
+pub trait RoundWithMode: Sized {
+    fn round_with_mode(&self, number_decimals: usize, mode: RoundingMode) -> Self;
+}
+
+/// Rounds the shortest decimal representation of the value, so 2.675 rounds half up to
+/// 2.68 even though the nearest `f64` is slightly below 2.675. NaN and infinities are
+/// returned unchanged.
+impl RoundWithMode for f64 {
+    fn round_with_mode(&self, number_decimals: usize, mode: RoundingMode) -> Self {
+        if !self.is_finite() {
+            return *self;
+        }
+
+        let decimal = BigDecimal::from_str(&self.to_string()).unwrap();
+        let rounded = decimal
+            .round_with_mode(number_decimals.min(MAX_F64_DECIMALS), mode)
+            .to_f64()
+            .unwrap();
+
+        // Avoid returning -0.0 for small negative values that round to zero.
+        if rounded == 0.0 {
+            0.0
+        } else {
+            rounded
+        }
+    }
+}
+
+/// A value that already has no more than the number of decimal places is returned unchanged,
+/// without padding it with zeros.
+impl RoundWithMode for BigDecimal {
+    fn round_with_mode(&self, number_decimals: usize, mode: RoundingMode) -> Self {
+        match i64::try_from(number_decimals) {
+            Ok(scale) if scale < self.fractional_digit_count() => self.with_scale_round(scale, mode.into()),
+            _ => self.clone(),
+        }
+    }
+}
+
+/// Round the value to a number of decimal places using the rounding mode.
+pub fn round_with_mode<T: RoundWithMode>(value: T, number_decimals: usize, mode: RoundingMode) -> T {
+    value.round_with_mode(number_decimals, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(RoundingMode::HalfUp, [3.0, 2.0, -2.0, -3.0, 2.0, 3.0])]
+    #[case(RoundingMode::HalfDown, [2.0, 2.0, -2.0, -2.0, 2.0, 3.0])]
+    #[case(RoundingMode::HalfEven, [2.0, 2.0, -2.0, -2.0, 2.0, 3.0])]
+    #[case(RoundingMode::Ceiling, [3.0, 3.0, -2.0, -2.0, 3.0, 3.0])]
+    #[case(RoundingMode::Floor, [2.0, 2.0, -3.0, -3.0, 2.0, 3.0])]
+    #[case(RoundingMode::TowardZero, [2.0, 2.0, -2.0, -2.0, 2.0, 3.0])]
+    #[case(RoundingMode::AwayFromZero, [3.0, 3.0, -3.0, -3.0, 3.0, 3.0])]
+    fn test_round_with_mode_f64(#[case] mode: RoundingMode, #[case] expected: [f64; 6]) {
+        let values = [2.5, 2.1, -2.1, -2.5, 2.4, 3.0];
+        let actual = values.map(|value| round_with_mode(value, 0, mode));
+        assert_eq!(actual, expected, "Failed for {:?}", mode);
+    }
+
+    #[rstest]
+    #[case(3.5, RoundingMode::HalfEven, 4.0)]
+    #[case(-3.5, RoundingMode::HalfEven, -4.0)]
+    #[case(-2.5, RoundingMode::HalfDown, -2.0)]
+    fn test_round_with_mode_f64_halves(#[case] value: f64, #[case] mode: RoundingMode, #[case] expected: f64) {
+        assert_eq!(round_with_mode(value, 0, mode), expected);
+    }
+
+    #[rstest]
+    #[case(2.675, 2, RoundingMode::HalfUp, 2.68)]
+    #[case(2.675, 2, RoundingMode::HalfEven, 2.68)]
+    #[case(2.665, 2, RoundingMode::HalfEven, 2.66)]
+    #[case(1.005, 2, RoundingMode::HalfUp, 1.01)]
+    #[case(1.005, 2, RoundingMode::HalfDown, 1.0)]
+    #[case(0.12345, 4, RoundingMode::HalfEven, 0.1234)]
+    #[case(-0.00004, 4, RoundingMode::HalfUp, 0.0)]
+    #[case(-0.00004, 4, RoundingMode::Floor, -0.0001)]
+    #[case(123.456, 6, RoundingMode::HalfUp, 123.456)]
+    #[case(2.345, usize::MAX, RoundingMode::HalfUp, 2.345)]
+    #[case(1.5, 100_000_000, RoundingMode::HalfUp, 1.5)]
+    #[case(5e-324, 400, RoundingMode::Ceiling, 5e-324)]
+    fn test_round_with_mode_f64_decimals(
+        #[case] value: f64,
+        #[case] number_decimals: usize,
+        #[case] mode: RoundingMode,
+        #[case] expected: f64,
+    ) {
+        assert_eq!(round_with_mode(value, number_decimals, mode), expected);
+    }
+
+    #[test]
+    fn test_round_with_mode_f64_not_finite() {
+        assert!(round_with_mode(f64::NAN, 2, RoundingMode::HalfUp).is_nan());
+        assert_eq!(round_with_mode(f64::INFINITY, 2, RoundingMode::HalfUp), f64::INFINITY);
+    }
+
+    #[rstest]
+    #[case("2.345", 2, RoundingMode::HalfUp, "2.35")]
+    #[case("2.345", 2, RoundingMode::HalfDown, "2.34")]
+    #[case("2.345", 2, RoundingMode::HalfEven, "2.34")]
+    #[case("2.355", 2, RoundingMode::HalfEven, "2.36")]
+    #[case("-2.341", 2, RoundingMode::Ceiling, "-2.34")]
+    #[case("-2.341", 2, RoundingMode::Floor, "-2.35")]
+    #[case("-2.349", 2, RoundingMode::TowardZero, "-2.34")]
+    #[case("-2.341", 2, RoundingMode::AwayFromZero, "-2.35")]
+    #[case("2.3", 4, RoundingMode::HalfUp, "2.3")]
+    #[case("12E+3", 2, RoundingMode::HalfUp, "12E+3")]
+    #[case("2.3", 100_000_000, RoundingMode::HalfUp, "2.3")]
+    #[case("1.123456789012345678901234567890", 25, RoundingMode::HalfUp, "1.1234567890123456789012346")]
+    #[case("2.345", usize::MAX, RoundingMode::HalfUp, "2.345")]
+    fn test_round_with_mode_big_decimal(
+        #[case] value: &str,
+        #[case] number_decimals: usize,
+        #[case] mode: RoundingMode,
+        #[case] expected: &str,
+    ) {
+        let value = BigDecimal::from_str(value).unwrap();
+        let expected = BigDecimal::from_str(expected).unwrap();
+        let actual = round_with_mode(value, number_decimals, mode);
+        assert_eq!(actual, expected);
+        assert_eq!(actual.to_string(), expected.to_string());
+    }
+}
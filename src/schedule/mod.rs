@@ -0,0 +1,2 @@
+pub mod weekly_schedule;
+pub use weekly_schedule::*;
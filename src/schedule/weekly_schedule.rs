@@ -0,0 +1,206 @@
+use crate::daterange::DateRange;
+use crate::datetimerange::DateTimeRange;
+use crate::timerange::{TimeRange, TimeRangeSet};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use std::collections::BTreeMap;
+
+/// Hours that replace the weekly hours on a specific date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleException {
+    /// Closed all day, e.g. a holiday.
+    Closed,
+    /// Open only during these ranges, e.g. special holiday hours.
+    Hours(Vec<TimeRange>),
+}
+
+/// Recurring weekly hours, such as store hours or employee availability, with
+/// date-specific exceptions.
+///
+/// Ranges that cross midnight belong to the day they start on, so Friday 18:00-02:00
+/// is open until 02:00 on Saturday.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WeeklySchedule {
+    days: [Vec<TimeRange>; 7],
+    exceptions: BTreeMap<NaiveDate, ScheduleException>,
+}
+
+impl WeeklySchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a range of hours on the day of the week.
+    pub fn add(&mut self, day: Weekday, range: TimeRange) {
+        let ranges = &mut self.days[day.num_days_from_monday() as usize];
+        ranges.push(range);
+        ranges.sort();
+    }
+
+    /// Replace the weekly hours on a specific date.
+    pub fn add_exception(&mut self, date: NaiveDate, exception: ScheduleException) {
+        self.exceptions.insert(date, exception);
+    }
+
+    /// Get the weekly hours for the day of the week.
+    pub fn hours_for(&self, day: Weekday) -> &[TimeRange] {
+        &self.days[day.num_days_from_monday() as usize]
+    }
+
+    /// Get the exception for the date, if there is one.
+    pub fn exception_for(&self, date: NaiveDate) -> Option<&ScheduleException> {
+        self.exceptions.get(&date)
+    }
+
+    /// Get the hours that start on the date, taking exceptions into account.
+    pub fn hours_on_date(&self, date: NaiveDate) -> &[TimeRange] {
+        match self.exceptions.get(&date) {
+            Some(ScheduleException::Closed) => &[],
+            Some(ScheduleException::Hours(ranges)) => ranges,
+            None => self.hours_for(date.weekday()),
+        }
+    }
+
+    /// Get the hours that start on the date as `DateTimeRange`s.
+    pub fn on_date(&self, date: NaiveDate) -> Vec<DateTimeRange> {
+        self.hours_on_date(date).iter().map(|range| range.on_date(date)).collect()
+    }
+
+    /// Check if the schedule is open at the date-time. Ranges include their start
+    /// but not their end.
+    pub fn is_open(&self, date_time: NaiveDateTime) -> bool {
+        let date = date_time.date();
+        self.on_date(date - Duration::days(1))
+            .into_iter()
+            .chain(self.on_date(date))
+            .any(|range| range.start() <= date_time && date_time < range.end())
+    }
+
+    /// Get the first date-time at or after `date_time` when the schedule is open.
+    /// Returns `None` if the schedule is never open again.
+    pub fn next_open_after(&self, date_time: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.is_open(date_time) {
+            return Some(date_time);
+        }
+
+        // After the last exception the schedule repeats weekly, so a week past it is enough.
+        let last_date = self
+            .exceptions
+            .keys()
+            .next_back()
+            .copied()
+            .map_or(date_time.date(), |last| last.max(date_time.date()))
+            + Duration::days(7);
+
+        let mut date = date_time.date();
+        while date <= last_date {
+            let next = self
+                .on_date(date)
+                .into_iter()
+                .map(|range| range.start())
+                .filter(|start| *start > date_time)
+                .min();
+            if next.is_some() {
+                return next;
+            }
+            date += Duration::days(1);
+        }
+        None
+    }
+
+    /// Get the hours that start on each date in the range, in order.
+    pub fn materialize(&self, date_range: &DateRange) -> Vec<DateTimeRange> {
+        let mut ranges: Vec<DateTimeRange> = date_range.iter().flat_map(|date| self.on_date(date)).collect();
+        ranges.sort();
+        ranges
+    }
+
+    /// Get the total weekly hours, ignoring exceptions. Overlapping ranges on the same
+    /// day are only counted once.
+    pub fn total_weekly_hours(&self) -> Duration {
+        self.days.iter().map(|ranges| TimeRangeSet::from_ranges(ranges).duration()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> TimeRange {
+        TimeRange::of(
+            NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+        )
+    }
+
+    // 2025-08-18 is a Monday
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn store_hours() -> WeeklySchedule {
+        let mut schedule = WeeklySchedule::new();
+        schedule.add(Weekday::Mon, range((9, 0), (17, 0)));
+        schedule.add(Weekday::Tue, range((13, 0), (18, 0)));
+        schedule.add(Weekday::Tue, range((9, 0), (12, 0)));
+        schedule.add(Weekday::Fri, range((18, 0), (2, 0)));
+        schedule.add_exception(NaiveDate::from_ymd_opt(2025, 8, 25).unwrap(), ScheduleException::Closed);
+        schedule.add_exception(
+            NaiveDate::from_ymd_opt(2025, 8, 26).unwrap(),
+            ScheduleException::Hours(vec![range((10, 0), (14, 0))]),
+        );
+        schedule
+    }
+
+    #[rstest]
+    #[case(at(18, 9, 0), true)]
+    #[case(at(18, 17, 0), false)]
+    #[case(at(19, 12, 30), false)]
+    #[case(at(19, 13, 0), true)]
+    #[case(at(22, 23, 0), true)]
+    #[case(at(23, 1, 59), true)]
+    #[case(at(23, 2, 0), false)]
+    #[case(at(25, 10, 0), false)]
+    #[case(at(26, 9, 0), false)]
+    #[case(at(26, 13, 30), true)]
+    fn test_is_open(#[case] date_time: NaiveDateTime, #[case] expected: bool) {
+        assert_eq!(store_hours().is_open(date_time), expected);
+    }
+
+    #[rstest]
+    #[case(at(18, 10, 0), Some(at(18, 10, 0)))]
+    #[case(at(18, 17, 0), Some(at(19, 9, 0)))]
+    #[case(at(19, 12, 0), Some(at(19, 13, 0)))]
+    #[case(at(23, 3, 0), Some(at(26, 10, 0)))]
+    #[case(at(26, 14, 0), Some(at(29, 18, 0)))]
+    fn test_next_open_after(#[case] date_time: NaiveDateTime, #[case] expected: Option<NaiveDateTime>) {
+        assert_eq!(store_hours().next_open_after(date_time), expected);
+    }
+
+    #[test]
+    fn test_next_open_after_never_open() {
+        assert_eq!(WeeklySchedule::new().next_open_after(at(18, 0, 0)), None);
+    }
+
+    #[test]
+    fn test_materialize() {
+        let week = DateRange::new(at(22, 0, 0).date(), at(26, 0, 0).date());
+        assert_eq!(
+            store_hours().materialize(&week),
+            vec![
+                DateTimeRange::of(at(22, 18, 0), at(23, 2, 0)),
+                DateTimeRange::of(at(26, 10, 0), at(26, 14, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_total_weekly_hours() {
+        let mut schedule = store_hours();
+        assert_eq!(schedule.total_weekly_hours(), Duration::hours(8 + 3 + 5 + 8));
+
+        schedule.add(Weekday::Mon, range((16, 0), (19, 0)));
+        assert_eq!(schedule.total_weekly_hours(), Duration::hours(10 + 3 + 5 + 8));
+    }
+}
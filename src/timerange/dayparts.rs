@@ -0,0 +1,238 @@
+use crate::datetimerange::DateTimeRange;
+use crate::timerange::TimeRange;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+
+/// A named part of the day, such as breakfast or late night.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Daypart {
+    name: String,
+    time_range: TimeRange,
+}
+
+impl Daypart {
+    pub fn of(name: &str, time_range: TimeRange) -> Self {
+        Self {
+            name: name.to_string(),
+            time_range,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn time_range(&self) -> &TimeRange {
+        &self.time_range
+    }
+}
+
+/// The dayparts that labor is reported by. A daypart that crosses midnight belongs to the
+/// date it starts on, so late night 22:00-02:00 on Friday includes 01:00 on Saturday.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dayparts {
+    dayparts: Vec<Daypart>,
+}
+
+impl Dayparts {
+    pub fn of(dayparts: Vec<Daypart>) -> Self {
+        Self { dayparts }
+    }
+
+    pub fn dayparts(&self) -> &[Daypart] {
+        &self.dayparts
+    }
+
+    /// Get the first daypart that the date-time falls in, including its start but not its
+    /// end.
+    pub fn classify(&self, date_time: NaiveDateTime) -> Option<&Daypart> {
+        self.dayparts.iter().find(|daypart| {
+            self.occurrences(daypart, date_time.date(), date_time.date())
+                .any(|(_, range)| range.start() <= date_time && date_time < range.end())
+        })
+    }
+
+    /// Get the time the range spends in each daypart, in daypart order. Dayparts the range
+    /// does not touch are left out.
+    pub fn split(&self, range: &DateTimeRange) -> Vec<(&Daypart, Duration)> {
+        self.dayparts
+            .iter()
+            .map(|daypart| {
+                let duration = self
+                    .split_by_date(daypart, range)
+                    .map(|(_, duration)| duration)
+                    .sum::<Duration>();
+                (daypart, duration)
+            })
+            .filter(|(_, duration)| *duration > Duration::zero())
+            .collect()
+    }
+
+    /// Add up the time the shifts spend in each daypart on each date.
+    pub fn aggregate(&self, shifts: &[DateTimeRange]) -> DaypartMatrix {
+        let mut values: BTreeMap<NaiveDate, Vec<Duration>> = BTreeMap::new();
+
+        for shift in shifts {
+            for (index, daypart) in self.dayparts.iter().enumerate() {
+                for (date, duration) in self.split_by_date(daypart, shift) {
+                    values
+                        .entry(date)
+                        .or_insert_with(|| vec![Duration::zero(); self.dayparts.len()])[index] += duration;
+                }
+            }
+        }
+
+        DaypartMatrix {
+            names: self.dayparts.iter().map(|daypart| daypart.name.clone()).collect(),
+            values,
+        }
+    }
+
+    /// Get the daypart's occurrences that start on the dates, plus the one from the day
+    /// before in case it runs past midnight.
+    fn occurrences<'a>(
+        &self,
+        daypart: &'a Daypart,
+        first: NaiveDate,
+        last: NaiveDate,
+    ) -> impl Iterator<Item = (NaiveDate, DateTimeRange)> + 'a {
+        let first = first - Duration::days(1);
+        first
+            .iter_days()
+            .take_while(move |date| *date <= last)
+            .map(move |date| (date, daypart.time_range.on_date(date)))
+    }
+
+    fn split_by_date<'a>(
+        &self,
+        daypart: &'a Daypart,
+        range: &'a DateTimeRange,
+    ) -> impl Iterator<Item = (NaiveDate, Duration)> + 'a {
+        self.occurrences(daypart, range.start().date(), range.end().date())
+            .map(move |(date, occurrence)| (date, occurrence.overlap_duration(range)))
+            .filter(|(_, duration)| *duration > Duration::zero())
+    }
+}
+
+/// Time spent in each daypart on each date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaypartMatrix {
+    names: Vec<String>,
+    values: BTreeMap<NaiveDate, Vec<Duration>>,
+}
+
+impl DaypartMatrix {
+    /// Get the daypart names, in daypart order.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Get the dates that have time in at least one daypart, in order.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        self.values.keys().copied().collect()
+    }
+
+    pub fn get(&self, name: &str, date: NaiveDate) -> Duration {
+        match (self.names.iter().position(|n| n == name), self.values.get(&date)) {
+            (Some(index), Some(row)) => row[index],
+            _ => Duration::zero(),
+        }
+    }
+
+    /// Get the time in each daypart on the date, in daypart order.
+    pub fn row(&self, date: NaiveDate) -> Vec<Duration> {
+        self.values
+            .get(&date)
+            .cloned()
+            .unwrap_or_else(|| vec![Duration::zero(); self.names.len()])
+    }
+
+    pub fn total_for_daypart(&self, name: &str) -> Duration {
+        self.values.keys().map(|date| self.get(name, *date)).sum()
+    }
+
+    pub fn total_for_date(&self, date: NaiveDate) -> Duration {
+        self.row(date).into_iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+    use rstest::rstest;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> TimeRange {
+        TimeRange::of(
+            NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+        )
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 8, day).unwrap()
+    }
+
+    fn restaurant() -> Dayparts {
+        Dayparts::of(vec![
+            Daypart::of("Breakfast", range((6, 0), (11, 0))),
+            Daypart::of("Lunch", range((11, 0), (15, 0))),
+            Daypart::of("Dinner", range((16, 0), (22, 0))),
+            Daypart::of("Late Night", range((22, 0), (2, 0))),
+        ])
+    }
+
+    #[rstest]
+    #[case(at(18, 6, 0), Some("Breakfast"))]
+    #[case(at(18, 11, 0), Some("Lunch"))]
+    #[case(at(18, 15, 30), None)]
+    #[case(at(18, 23, 0), Some("Late Night"))]
+    #[case(at(19, 1, 59), Some("Late Night"))]
+    #[case(at(19, 2, 0), None)]
+    fn test_classify(#[case] date_time: NaiveDateTime, #[case] expected: Option<&str>) {
+        let dayparts = restaurant();
+        assert_eq!(dayparts.classify(date_time).map(|daypart| daypart.name()), expected);
+    }
+
+    #[rstest]
+    #[case(DateTimeRange::of(at(18, 10, 0), at(18, 14, 0)), vec![("Breakfast", 60), ("Lunch", 180)])]
+    #[case(DateTimeRange::of(at(18, 14, 0), at(19, 1, 0)), vec![("Lunch", 60), ("Dinner", 360), ("Late Night", 180)])]
+    #[case(DateTimeRange::of(at(18, 15, 0), at(18, 16, 0)), vec![])]
+    fn test_split(#[case] shift: DateTimeRange, #[case] expected: Vec<(&str, i64)>) {
+        let dayparts = restaurant();
+        let actual: Vec<(&str, i64)> = dayparts
+            .split(&shift)
+            .into_iter()
+            .map(|(daypart, duration)| (daypart.name(), duration.num_minutes()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let shifts = vec![
+            DateTimeRange::of(at(18, 10, 0), at(18, 14, 0)),
+            DateTimeRange::of(at(18, 20, 0), at(19, 2, 0)),
+            DateTimeRange::of(at(19, 0, 0), at(19, 7, 0)),
+        ];
+        let matrix = restaurant().aggregate(&shifts);
+
+        assert_eq!(matrix.dates(), vec![date(18), date(19)]);
+        assert_eq!(matrix.get("Breakfast", date(18)), Duration::hours(1));
+        assert_eq!(matrix.get("Lunch", date(18)), Duration::hours(3));
+        assert_eq!(matrix.get("Dinner", date(18)), Duration::hours(2));
+        assert_eq!(matrix.get("Late Night", date(18)), Duration::hours(6));
+        assert_eq!(matrix.get("Breakfast", date(19)), Duration::hours(1));
+        assert_eq!(matrix.get("Late Night", date(19)), Duration::zero());
+        assert_eq!(matrix.total_for_daypart("Late Night"), Duration::hours(6));
+        assert_eq!(matrix.total_for_date(date(18)), Duration::hours(12));
+        assert_eq!(matrix.row(date(20)), vec![Duration::zero(); 4]);
+    }
+}
@@ -1,2 +1,8 @@
 pub mod time_range;
 pub use time_range::*;
+pub mod time_range_set;
+pub use time_range_set::*;
+pub mod dayparts;
+pub use dayparts::*;
+pub mod time_range_format;
+pub use time_range_format::*;
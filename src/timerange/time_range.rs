@@ -1,7 +1,15 @@
-use chrono::{Duration, NaiveTime};
+use crate::datetimerange::DateTimeRange;
+use chrono::{Duration, NaiveDate, NaiveTime, Timelike};
 use std::cmp::Ordering;
 use std::hash::{Hash, Hasher};
 
+pub(crate) const SECONDS_PER_DAY: u32 = 86_400;
+
+/// A range of times within a day.
+///
+/// A range whose end is before its start wraps past midnight, so 22:00-06:00 is an
+/// eight-hour overnight range. An end of 00:00 means the end of the day (24:00), so
+/// 22:00-00:00 is two hours long and 00:00-00:00 is the whole day.
 #[derive(Debug, Clone)]
 pub struct TimeRange {
     start: NaiveTime,
@@ -22,26 +30,148 @@ impl TimeRange {
     }
 
     pub fn duration(&self) -> Duration {
-        self.end - self.start
+        let seconds: u32 = self.segments().iter().map(|(start, end)| end - start).sum();
+        Duration::seconds(seconds as i64)
+    }
+
+    /// Check if the range continues past midnight into the next day.
+    pub fn crosses_midnight(&self) -> bool {
+        self.end_seconds() < seconds_of(self.start)
+    }
+
+    /// Check if the time is in the range, including the start but not the end, like
+    /// `TimeRangeSet::contains`. So 09:00-17:00 contains 09:00 but not 17:00, and 22:00-06:00
+    /// contains midnight.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        let time = seconds_of(time);
+        self.segments().iter().any(|(start, end)| *start <= time && time < *end)
+    }
+
+    /// Check if the time is in the range, excluding the start and end.
+    pub fn contains_exclusive(&self, time: NaiveTime) -> bool {
+        let start = seconds_of(self.start);
+        let end = self.end_seconds();
+        let time = seconds_of(time);
+
+        if self.crosses_midnight() {
+            time > start || time < end
+        } else {
+            time > start && time < end
+        }
     }
 
+    /// Get the times that are in both ranges. Two overnight ranges can intersect in two
+    /// separate pieces, e.g. 22:00-06:00 and 05:00-23:00 intersect in 05:00-06:00 and 22:00-23:00.
+    pub fn intersection(&self, other: &TimeRange) -> Vec<TimeRange> {
+        from_segments(&intersect_segments(&self.segments(), &other.segments()))
+    }
+
+    /// Check if the ranges share any time, including ranges that only touch at an end, like
+    /// `DateTimeRange::overlaps`. Overnight ranges are compared as the times they cover, so
+    /// 22:00-06:00 overlaps 01:00-02:00, and a range ending at midnight touches one starting
+    /// at midnight.
     pub fn overlaps(&self, other: &TimeRange) -> bool {
-        let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        let a = self.closed_segments();
+        let b = other.closed_segments();
+        a.iter().any(|(a_start, a_end)| {
+            b.iter().any(|(b_start, b_end)| {
+                (a_start <= b_end && b_start <= a_end)
+                    || (*a_end == SECONDS_PER_DAY && *b_start == 0)
+                    || (*b_end == SECONDS_PER_DAY && *a_start == 0)
+            })
+        })
+    }
 
-        if self.end == midnight && other.end == midnight {
-            return true;
+    /// Get the range on a date, ending on the next day if the range crosses midnight.
+    pub fn on_date(&self, date: NaiveDate) -> DateTimeRange {
+        if self.start == NaiveTime::MIN && self.end == NaiveTime::MIN {
+            DateTimeRange::all_day(date)
+        } else {
+            DateTimeRange::from_time_range_on_date(self.start, self.end, date)
         }
+    }
+
+    /// Get the segments including their ends, with an empty range as the single time it is at.
+    fn closed_segments(&self) -> Vec<(u32, u32)> {
+        let segments = self.segments();
+        if segments.is_empty() {
+            let start = seconds_of(self.start);
+            vec![(start, start)]
+        } else {
+            segments
+        }
+    }
+
+    /// Get the range as sorted, non-overlapping `[start, end)` second-of-day segments,
+    /// where the end can be `SECONDS_PER_DAY`.
+    pub(crate) fn segments(&self) -> Vec<(u32, u32)> {
+        let start = seconds_of(self.start);
+        let end = self.end_seconds();
+
+        if end > start {
+            vec![(start, end)]
+        } else if end == start {
+            vec![]
+        } else if end == 0 {
+            vec![(start, SECONDS_PER_DAY)]
+        } else {
+            vec![(0, end), (start, SECONDS_PER_DAY)]
+        }
+    }
 
-        if self.end == midnight {
-            return other.end >= self.start;
+    fn end_seconds(&self) -> u32 {
+        if self.end == NaiveTime::MIN {
+            SECONDS_PER_DAY
+        } else {
+            seconds_of(self.end)
         }
+    }
+}
+
+fn seconds_of(time: NaiveTime) -> u32 {
+    time.num_seconds_from_midnight()
+}
 
-        if other.end == midnight {
-            return other.start <= self.end;
+fn time_of(seconds: u32) -> NaiveTime {
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds % SECONDS_PER_DAY, 0).unwrap()
+}
+
+/// Intersect two lists of sorted, non-overlapping segments.
+pub(crate) fn intersect_segments(a: &[(u32, u32)], b: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start < end {
+            result.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
         }
+    }
+    result
+}
+
+/// Convert sorted, non-overlapping segments to `TimeRange`s, joining a segment that ends
+/// at midnight with one that starts at midnight into a single overnight range.
+pub(crate) fn from_segments(segments: &[(u32, u32)]) -> Vec<TimeRange> {
+    let mut segments = segments.to_vec();
+    let mut overnight = None;
 
-        self.start <= other.end && self.end >= other.start
+    if segments.len() > 1 && segments[0].0 == 0 && segments[segments.len() - 1].1 == SECONDS_PER_DAY {
+        let (_, end) = segments.remove(0);
+        let (start, _) = segments.pop().unwrap();
+        overnight = Some(TimeRange::of(time_of(start), time_of(end)));
     }
+
+    segments
+        .into_iter()
+        .map(|(start, end)| TimeRange::of(time_of(start), time_of(end)))
+        .chain(overnight)
+        .collect()
 }
 
 impl PartialEq for TimeRange {
@@ -73,3 +203,97 @@ impl Ord for TimeRange {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> TimeRange {
+        TimeRange::of(time(start.0, start.1), time(end.0, end.1))
+    }
+
+    #[rstest]
+    #[case(range((9, 0), (17, 0)), 8 * 60, false)]
+    #[case(range((22, 0), (6, 0)), 8 * 60, true)]
+    #[case(range((21, 30), (5, 45)), 8 * 60 + 15, true)]
+    #[case(range((22, 0), (0, 0)), 2 * 60, false)]
+    #[case(range((0, 0), (0, 0)), 24 * 60, false)]
+    #[case(range((9, 0), (9, 0)), 0, false)]
+    fn test_duration(#[case] range: TimeRange, #[case] expected_minutes: i64, #[case] expected_crosses_midnight: bool) {
+        assert_eq!(range.duration(), Duration::minutes(expected_minutes));
+        assert_eq!(range.crosses_midnight(), expected_crosses_midnight);
+    }
+
+    #[rstest]
+    #[case(range((9, 0), (17, 0)), time(9, 0), true, false)]
+    #[case(range((9, 0), (17, 0)), time(12, 0), true, true)]
+    #[case(range((9, 0), (17, 0)), time(17, 0), false, false)]
+    #[case(range((9, 0), (17, 0)), time(18, 0), false, false)]
+    #[case(range((22, 0), (6, 0)), time(23, 0), true, true)]
+    #[case(range((22, 0), (6, 0)), time(0, 0), true, true)]
+    #[case(range((22, 0), (6, 0)), time(22, 0), true, false)]
+    #[case(range((22, 0), (6, 0)), time(6, 0), false, false)]
+    #[case(range((22, 0), (6, 0)), time(12, 0), false, false)]
+    #[case(range((22, 0), (0, 0)), time(23, 59), true, true)]
+    #[case(range((22, 0), (0, 0)), time(0, 0), false, false)]
+    #[case(range((0, 0), (0, 0)), time(0, 0), true, false)]
+    #[case(range((0, 0), (0, 0)), time(12, 0), true, true)]
+    #[case(range((9, 0), (9, 0)), time(9, 0), false, false)]
+    fn test_contains(
+        #[case] range: TimeRange,
+        #[case] time: NaiveTime,
+        #[case] expected: bool,
+        #[case] expected_exclusive: bool,
+    ) {
+        assert_eq!(range.contains(time), expected);
+        assert_eq!(range.contains_exclusive(time), expected_exclusive);
+    }
+
+    #[rstest]
+    #[case(range((9, 0), (17, 0)), range((12, 0), (20, 0)), vec![range((12, 0), (17, 0))])]
+    #[case(range((9, 0), (12, 0)), range((12, 0), (20, 0)), vec![])]
+    #[case(range((22, 0), (6, 0)), range((5, 0), (23, 0)), vec![range((5, 0), (6, 0)), range((22, 0), (23, 0))])]
+    #[case(range((22, 0), (6, 0)), range((20, 0), (2, 0)), vec![range((22, 0), (2, 0))])]
+    #[case(range((22, 0), (6, 0)), range((0, 0), (0, 0)), vec![range((22, 0), (6, 0))])]
+    #[case(range((18, 0), (0, 0)), range((20, 0), (2, 0)), vec![range((20, 0), (0, 0))])]
+    fn test_intersection(#[case] a: TimeRange, #[case] b: TimeRange, #[case] expected: Vec<TimeRange>) {
+        assert_eq!(a.intersection(&b), expected);
+        assert_eq!(b.intersection(&a), expected);
+    }
+
+    #[rstest]
+    #[case(range((9, 0), (17, 0)), range((12, 0), (20, 0)), true)]
+    #[case(range((9, 0), (12, 0)), range((12, 0), (20, 0)), true)]
+    #[case(range((9, 0), (12, 0)), range((13, 0), (20, 0)), false)]
+    #[case(range((22, 0), (6, 0)), range((1, 0), (2, 0)), true)]
+    #[case(range((22, 0), (6, 0)), range((23, 0), (23, 30)), true)]
+    #[case(range((22, 0), (6, 0)), range((7, 0), (21, 0)), false)]
+    #[case(range((22, 0), (6, 0)), range((6, 0), (22, 0)), true)]
+    #[case(range((22, 0), (6, 0)), range((20, 0), (2, 0)), true)]
+    #[case(range((18, 0), (0, 0)), range((20, 0), (0, 0)), true)]
+    #[case(range((18, 0), (0, 0)), range((0, 0), (2, 0)), true)]
+    #[case(range((18, 0), (0, 0)), range((1, 0), (2, 0)), false)]
+    #[case(range((0, 0), (0, 0)), range((1, 0), (2, 0)), true)]
+    #[case(range((9, 0), (9, 0)), range((8, 0), (10, 0)), true)]
+    fn test_overlaps(#[case] a: TimeRange, #[case] b: TimeRange, #[case] expected: bool) {
+        assert_eq!(a.overlaps(&b), expected);
+        assert_eq!(b.overlaps(&a), expected);
+    }
+
+    #[test]
+    fn test_on_date() {
+        let date = NaiveDate::from_ymd_opt(2025, 8, 20).unwrap();
+
+        assert_eq!(
+            range((22, 0), (6, 0)).on_date(date),
+            DateTimeRange::from_time_range_on_date(time(22, 0), time(6, 0), date)
+        );
+        assert_eq!(range((0, 0), (0, 0)).on_date(date), DateTimeRange::all_day(date));
+        assert_eq!(range((22, 0), (0, 0)).on_date(date).duration(), Duration::hours(2));
+    }
+}
@@ -0,0 +1,350 @@
+use crate::datetimerange::DateTimeRange;
+use crate::timerange::TimeRange;
+use chrono::{NaiveDate, NaiveTime, Timelike};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the times in a range are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ClockStyle {
+    /// `09:00`, `17:30`
+    #[default]
+    TwentyFourHour,
+    /// `9:00 AM`, `5:30 PM`
+    TwelveHour,
+    /// `9a`, `5:30p`
+    Compact,
+}
+
+/// A format spec for writing `TimeRange`s and `DateTimeRange`s.
+///
+/// Parsing does not need a format: every style and separator is accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimeRangeFormat {
+    clock: ClockStyle,
+    separator: String,
+    next_day_marker: bool,
+}
+
+impl TimeRangeFormat {
+    pub fn of(clock: ClockStyle, separator: &str, next_day_marker: bool) -> Self {
+        Self {
+            clock,
+            separator: separator.to_string(),
+            next_day_marker,
+        }
+    }
+
+    /// `09:00-17:00`, `21:00-05:30`
+    pub fn twenty_four_hour() -> Self {
+        Self::of(ClockStyle::TwentyFourHour, "-", false)
+    }
+
+    /// `9:00 AM to 5:30 PM`
+    pub fn twelve_hour() -> Self {
+        Self::of(ClockStyle::TwelveHour, " to ", false)
+    }
+
+    /// `9a-5:30p`
+    pub fn compact() -> Self {
+        Self::of(ClockStyle::Compact, "-", false)
+    }
+
+    pub fn clock(&self) -> ClockStyle {
+        self.clock
+    }
+
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Check if ranges that end on the next day are written with a ` (+1)` marker.
+    pub fn next_day_marker(&self) -> bool {
+        self.next_day_marker
+    }
+
+    pub fn format(&self, range: &TimeRange) -> String {
+        let days = if ends_next_day(range) { 1 } else { 0 };
+        self.format_times(range.start(), range.end(), days, self.next_day_marker)
+    }
+
+    /// Write the range as its start date followed by its times, e.g. `2025-08-18 21:00-05:30 (+1)`.
+    /// A day marker is always written when it is needed to tell where the range ends.
+    pub fn format_date_time_range(&self, range: &DateTimeRange) -> String {
+        let date = range.start().date();
+        let time_range = TimeRange::of(range.start().time(), range.end().time());
+        let days = (range.end().date() - date).num_days();
+        let implied_days = if ends_next_day(&time_range) { 1 } else { 0 };
+        let marker = days != implied_days || self.next_day_marker;
+
+        format!(
+            "{} {}",
+            date.format("%Y-%m-%d"),
+            self.format_times(range.start().time(), range.end().time(), days, marker)
+        )
+    }
+
+    fn format_times(&self, start: NaiveTime, end: NaiveTime, days: i64, marker: bool) -> String {
+        let mut text = format!("{}{}{}", self.format_time(start), self.separator, self.format_time(end));
+        if marker && days > 0 {
+            text.push_str(&format!(" (+{})", days));
+        }
+        text
+    }
+
+    fn format_time(&self, time: NaiveTime) -> String {
+        let seconds = if time.second() > 0 {
+            format!(":{:02}", time.second())
+        } else {
+            String::new()
+        };
+
+        match self.clock {
+            ClockStyle::TwentyFourHour => format!("{:02}:{:02}{}", time.hour(), time.minute(), seconds),
+            ClockStyle::TwelveHour => {
+                let (pm, hour) = time.hour12();
+                let meridiem = if pm { "PM" } else { "AM" };
+                format!("{}:{:02}{} {}", hour, time.minute(), seconds, meridiem)
+            }
+            ClockStyle::Compact => {
+                let (pm, hour) = time.hour12();
+                let meridiem = if pm { "p" } else { "a" };
+                if time.minute() == 0 && time.second() == 0 {
+                    format!("{}{}", hour, meridiem)
+                } else {
+                    format!("{}:{:02}{}{}", hour, time.minute(), seconds, meridiem)
+                }
+            }
+        }
+    }
+}
+
+impl Default for TimeRangeFormat {
+    fn default() -> Self {
+        Self::twenty_four_hour()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeRangeParseError {
+    Empty,
+    /// No `-`, `–`, `—` or `to` between the start and end.
+    MissingSeparator(String),
+    InvalidTime(String),
+    InvalidDate(String),
+    InvalidDayMarker(String),
+    /// A `TimeRange` marked `(+1)` that does not end on the next day, or the other way round.
+    DayMarkerMismatch(String),
+}
+
+impl fmt::Display for TimeRangeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeRangeParseError::Empty => write!(f, "time range is empty"),
+            TimeRangeParseError::MissingSeparator(text) => {
+                write!(f, "time range '{}' has no separator between start and end", text)
+            }
+            TimeRangeParseError::InvalidTime(text) => write!(f, "'{}' is not a valid time", text),
+            TimeRangeParseError::InvalidDate(text) => write!(f, "'{}' is not a valid date", text),
+            TimeRangeParseError::InvalidDayMarker(text) => write!(f, "'{}' is not a valid day marker", text),
+            TimeRangeParseError::DayMarkerMismatch(text) => {
+                write!(f, "day marker does not match the times in '{}'", text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TimeRangeParseError {}
+
+/// Parses `09:00-17:00`, `9a-5p`, `9:00 AM to 5:30 PM` and `21:00–05:30 (+1)`.
+impl FromStr for TimeRange {
+    type Err = TimeRangeParseError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (start, end, days) = parse_times(text)?;
+        let range = TimeRange::of(start, end);
+        match days {
+            Some(1) if !ends_next_day(&range) => Err(TimeRangeParseError::DayMarkerMismatch(text.to_string())),
+            Some(days) if days > 1 => Err(TimeRangeParseError::DayMarkerMismatch(text.to_string())),
+            _ => Ok(range),
+        }
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", TimeRangeFormat::default().format(self))
+    }
+}
+
+/// Check if the range ends on the day after it starts.
+pub(crate) fn ends_next_day(range: &TimeRange) -> bool {
+    let date = NaiveDate::MIN;
+    range.on_date(date).end().date() > date
+}
+
+/// Split text into the start time, end time and day marker.
+pub(crate) fn parse_times(text: &str) -> Result<(NaiveTime, NaiveTime, Option<u32>), TimeRangeParseError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(TimeRangeParseError::Empty);
+    }
+
+    let (times, days) = match text.strip_suffix(')').and_then(|rest| rest.rsplit_once('(')) {
+        Some((times, marker)) => {
+            let days = marker
+                .trim()
+                .strip_prefix('+')
+                .and_then(|days| days.parse::<u32>().ok())
+                .filter(|days| *days > 0)
+                .ok_or_else(|| TimeRangeParseError::InvalidDayMarker(format!("({})", marker)))?;
+            (times.trim(), Some(days))
+        }
+        None => (text, None),
+    };
+
+    let lower = times.to_ascii_lowercase();
+    let (start, end) = match lower.find(" to ") {
+        Some(index) => (&times[..index], &times[index + 4..]),
+        None => times
+            .split_once(['-', '–', '—'])
+            .ok_or_else(|| TimeRangeParseError::MissingSeparator(text.to_string()))?,
+    };
+
+    Ok((parse_time(start)?, parse_time(end)?, days))
+}
+
+/// Parse `9`, `09:00`, `17:30:15`, `24:00`, `9a`, `9pm`, `9:30 PM` or `9:30 a.m.`.
+fn parse_time(text: &str) -> Result<NaiveTime, TimeRangeParseError> {
+    let invalid = || TimeRangeParseError::InvalidTime(text.trim().to_string());
+
+    let lower = text.trim().to_ascii_lowercase().replace('.', "");
+    let (clock, pm) = if let Some(clock) = lower.strip_suffix("am").or_else(|| lower.strip_suffix('a')) {
+        (clock.trim_end(), Some(false))
+    } else if let Some(clock) = lower.strip_suffix("pm").or_else(|| lower.strip_suffix('p')) {
+        (clock.trim_end(), Some(true))
+    } else {
+        (lower.as_str(), None)
+    };
+
+    let mut parts = clock.split(':');
+    let mut number = |required: bool| -> Result<u32, TimeRangeParseError> {
+        match parts.next() {
+            Some(part) if !part.is_empty() && part.len() <= 2 && part.bytes().all(|b| b.is_ascii_digit()) => {
+                Ok(part.parse().unwrap())
+            }
+            None if !required => Ok(0),
+            _ => Err(invalid()),
+        }
+    };
+    let hour = number(true)?;
+    let minute = number(false)?;
+    let second = number(false)?;
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return Err(invalid()),
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None if hour == 24 && minute == 0 && second == 0 => 0,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, second).ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use rstest::rstest;
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> TimeRange {
+        TimeRange::of(
+            NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+        )
+    }
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 8, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[rstest]
+    #[case("9a-5p", range((9, 0), (17, 0)))]
+    #[case("09:00-17:00", range((9, 0), (17, 0)))]
+    #[case("21:00–05:30 (+1)", range((21, 0), (5, 30)))]
+    #[case("21:00 - 05:30", range((21, 0), (5, 30)))]
+    #[case("9:00 AM to 5:30 PM", range((9, 0), (17, 30)))]
+    #[case("12a-12p", range((0, 0), (12, 0)))]
+    #[case("10:30 p.m. — 2 a.m.", range((22, 30), (2, 0)))]
+    #[case("18:00-24:00", range((18, 0), (0, 0)))]
+    #[case("00:00-00:00 (+1)", range((0, 0), (0, 0)))]
+    fn test_parse_time_range(#[case] text: &str, #[case] expected: TimeRange) {
+        assert_eq!(text.parse::<TimeRange>(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("", TimeRangeParseError::Empty)]
+    #[case("09:00", TimeRangeParseError::MissingSeparator("09:00".to_string()))]
+    #[case("9a-13p", TimeRangeParseError::InvalidTime("13p".to_string()))]
+    #[case("25:00-26:00", TimeRangeParseError::InvalidTime("25:00".to_string()))]
+    #[case("09:60-10:00", TimeRangeParseError::InvalidTime("09:60".to_string()))]
+    #[case("9a-5p (1)", TimeRangeParseError::InvalidDayMarker("(1)".to_string()))]
+    #[case("9a-5p (+1)", TimeRangeParseError::DayMarkerMismatch("9a-5p (+1)".to_string()))]
+    fn test_parse_time_range_invalid(#[case] text: &str, #[case] expected: TimeRangeParseError) {
+        assert_eq!(text.parse::<TimeRange>(), Err(expected));
+    }
+
+    #[rstest]
+    #[case(TimeRangeFormat::twenty_four_hour(), range((9, 0), (17, 30)), "09:00-17:30")]
+    #[case(TimeRangeFormat::twelve_hour(), range((9, 0), (17, 30)), "9:00 AM to 5:30 PM")]
+    #[case(TimeRangeFormat::compact(), range((9, 0), (17, 30)), "9a-5:30p")]
+    #[case(TimeRangeFormat::compact(), range((0, 0), (12, 0)), "12a-12p")]
+    #[case(TimeRangeFormat::twenty_four_hour(), range((21, 0), (5, 30)), "21:00-05:30")]
+    #[case(TimeRangeFormat::of(ClockStyle::TwentyFourHour, "–", true), range((21, 0), (5, 30)), "21:00–05:30 (+1)")]
+    #[case(TimeRangeFormat::of(ClockStyle::TwentyFourHour, "–", true), range((9, 0), (17, 0)), "09:00–17:00")]
+    fn test_format_time_range(#[case] format: TimeRangeFormat, #[case] range: TimeRange, #[case] expected: &str) {
+        assert_eq!(format.format(&range), expected);
+        assert_eq!(expected.parse::<TimeRange>(), Ok(range));
+    }
+
+    #[rstest]
+    #[case(
+        TimeRangeFormat::twenty_four_hour(),
+        DateTimeRange::of(at(18, 21, 0), at(19, 5, 30)),
+        "2025-08-18 21:00-05:30"
+    )]
+    #[case(
+        TimeRangeFormat::of(ClockStyle::TwentyFourHour, "-", true),
+        DateTimeRange::of(at(18, 21, 0), at(19, 5, 30)),
+        "2025-08-18 21:00-05:30 (+1)"
+    )]
+    #[case(
+        TimeRangeFormat::compact(),
+        DateTimeRange::of(at(18, 9, 0), at(19, 17, 0)),
+        "2025-08-18 9a-5p (+1)"
+    )]
+    #[case(
+        TimeRangeFormat::twelve_hour(),
+        DateTimeRange::of(at(18, 9, 0), at(18, 17, 0)),
+        "2025-08-18 9:00 AM to 5:00 PM"
+    )]
+    fn test_format_date_time_range(
+        #[case] format: TimeRangeFormat,
+        #[case] range: DateTimeRange,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format.format_date_time_range(&range), expected);
+        assert_eq!(expected.parse::<DateTimeRange>(), Ok(range));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(range((22, 0), (6, 0)).to_string(), "22:00-06:00");
+    }
+}
@@ -0,0 +1,197 @@
+use crate::timerange::{from_segments, intersect_segments, TimeRange};
+use chrono::{Duration, NaiveTime, Timelike};
+
+/// A union of `TimeRange`s within a day, kept as sorted, non-overlapping second-of-day
+/// segments. Ranges that wrap past midnight are split at midnight and joined back
+/// together when the ranges are read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TimeRangeSet {
+    segments: Vec<(u32, u32)>,
+}
+
+impl TimeRangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_ranges(ranges: &[TimeRange]) -> Self {
+        ranges.iter().cloned().collect()
+    }
+
+    /// Add a range, merging it with any ranges it overlaps or touches.
+    pub fn insert(&mut self, range: TimeRange) {
+        self.segments.extend(range.segments());
+        self.segments = merge_segments(std::mem::take(&mut self.segments));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Get the merged ranges in order of start time. A range that wraps past midnight
+    /// comes last.
+    pub fn ranges(&self) -> Vec<TimeRange> {
+        from_segments(&self.segments)
+    }
+
+    /// Get the total time covered, counting overlapping ranges once.
+    pub fn duration(&self) -> Duration {
+        Duration::seconds(self.segments.iter().map(|(start, end)| (end - start) as i64).sum())
+    }
+
+    /// Check if the time falls inside one of the ranges. Ranges include their start but
+    /// not their end.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        let seconds = time.num_seconds_from_midnight();
+        self.segments
+            .iter()
+            .any(|(start, end)| *start <= seconds && seconds < *end)
+    }
+
+    pub fn union(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        let segments = self.segments.iter().chain(&other.segments).copied().collect();
+        Self {
+            segments: merge_segments(segments),
+        }
+    }
+
+    pub fn intersection(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        Self {
+            segments: intersect_segments(&self.segments, &other.segments),
+        }
+    }
+
+    /// Get the time in this set that is not in `other`.
+    pub fn difference(&self, other: &TimeRangeSet) -> TimeRangeSet {
+        let mut segments = Vec::new();
+        let mut removed = other.segments.iter().peekable();
+
+        for &(start, end) in &self.segments {
+            let mut start = start;
+            while let Some(&&(removed_start, removed_end)) = removed.peek() {
+                if removed_end <= start {
+                    removed.next();
+                    continue;
+                }
+                if removed_start >= end {
+                    break;
+                }
+                if removed_start > start {
+                    segments.push((start, removed_start));
+                }
+                start = removed_end;
+                if removed_end > end {
+                    break;
+                }
+                removed.next();
+            }
+            if start < end {
+                segments.push((start, end));
+            }
+        }
+
+        Self { segments }
+    }
+}
+
+impl FromIterator<TimeRange> for TimeRangeSet {
+    fn from_iter<I: IntoIterator<Item = TimeRange>>(iter: I) -> Self {
+        let segments = iter.into_iter().flat_map(|range| range.segments()).collect();
+        Self {
+            segments: merge_segments(segments),
+        }
+    }
+}
+
+/// Sort segments and merge the ones that overlap or touch.
+fn merge_segments(mut segments: Vec<(u32, u32)>) -> Vec<(u32, u32)> {
+    segments.sort();
+
+    let mut merged: Vec<(u32, u32)> = Vec::with_capacity(segments.len());
+    for (start, end) in segments {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn range(start: HourMinute, end: HourMinute) -> TimeRange {
+        TimeRange::of(
+            NaiveTime::from_hms_opt(start.0, start.1, 0).unwrap(),
+            NaiveTime::from_hms_opt(end.0, end.1, 0).unwrap(),
+        )
+    }
+
+    type HourMinute = (u32, u32);
+
+    fn set(ranges: &[(HourMinute, HourMinute)]) -> TimeRangeSet {
+        ranges.iter().map(|(start, end)| range(*start, *end)).collect()
+    }
+
+    #[rstest]
+    #[case(set(&[((9, 0), (12, 0)), ((11, 0), (13, 0))]), vec![range((9, 0), (13, 0))], 240)]
+    #[case(set(&[((9, 0), (12, 0)), ((12, 0), (13, 0))]), vec![range((9, 0), (13, 0))], 240)]
+    #[case(set(&[((22, 0), (2, 0)), ((1, 0), (6, 0))]), vec![range((22, 0), (6, 0))], 480)]
+    #[case(set(&[((18, 0), (0, 0)), ((0, 0), (2, 0))]), vec![range((18, 0), (2, 0))], 480)]
+    #[case(set(&[((22, 0), (2, 0)), ((9, 0), (17, 0))]), vec![range((9, 0), (17, 0)), range((22, 0), (2, 0))], 720)]
+    #[case(set(&[((0, 0), (0, 0))]), vec![range((0, 0), (0, 0))], 1_440)]
+    #[case(set(&[((8, 0), (8, 0))]), vec![], 0)]
+    fn test_normalize(#[case] set: TimeRangeSet, #[case] expected: Vec<TimeRange>, #[case] expected_minutes: i64) {
+        assert_eq!(set.ranges(), expected);
+        assert_eq!(set.duration(), Duration::minutes(expected_minutes));
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = TimeRangeSet::new();
+        set.insert(range((13, 0), (18, 0)));
+        set.insert(range((9, 0), (12, 0)));
+        set.insert(range((12, 0), (13, 0)));
+        assert_eq!(set.ranges(), vec![range((9, 0), (18, 0))]);
+    }
+
+    #[rstest]
+    #[case(set(&[((9, 0), (12, 0))]), set(&[((13, 0), (17, 0))]), vec![range((9, 0), (12, 0)), range((13, 0), (17, 0))])]
+    #[case(set(&[((20, 0), (0, 0))]), set(&[((0, 0), (4, 0))]), vec![range((20, 0), (4, 0))])]
+    fn test_union(#[case] a: TimeRangeSet, #[case] b: TimeRangeSet, #[case] expected: Vec<TimeRange>) {
+        assert_eq!(a.union(&b).ranges(), expected);
+    }
+
+    #[rstest]
+    #[case(set(&[((9, 0), (17, 0))]), set(&[((12, 0), (20, 0))]), vec![range((12, 0), (17, 0))])]
+    #[case(set(&[((22, 0), (6, 0))]), set(&[((23, 0), (1, 0)), ((5, 0), (9, 0))]), vec![range((5, 0), (6, 0)), range((23, 0), (1, 0))])]
+    #[case(set(&[((9, 0), (12, 0))]), set(&[((12, 0), (17, 0))]), vec![])]
+    fn test_intersection(#[case] a: TimeRangeSet, #[case] b: TimeRangeSet, #[case] expected: Vec<TimeRange>) {
+        assert_eq!(a.intersection(&b).ranges(), expected);
+    }
+
+    #[rstest]
+    #[case(set(&[((9, 0), (17, 0))]), set(&[((12, 0), (13, 0))]), vec![range((9, 0), (12, 0)), range((13, 0), (17, 0))])]
+    #[case(set(&[((9, 0), (17, 0))]), set(&[((8, 0), (10, 0)), ((16, 0), (18, 0))]), vec![range((10, 0), (16, 0))])]
+    #[case(set(&[((22, 0), (6, 0))]), set(&[((0, 0), (1, 0))]), vec![range((1, 0), (6, 0)), range((22, 0), (0, 0))])]
+    #[case(set(&[((0, 0), (0, 0))]), set(&[((9, 0), (17, 0))]), vec![range((17, 0), (9, 0))])]
+    #[case(set(&[((9, 0), (17, 0))]), set(&[((0, 0), (0, 0))]), vec![])]
+    #[case(set(&[((9, 0), (10, 0)), ((11, 0), (12, 0))]), set(&[((9, 30), (11, 30))]), vec![range((9, 0), (9, 30)), range((11, 30), (12, 0))])]
+    fn test_difference(#[case] a: TimeRangeSet, #[case] b: TimeRangeSet, #[case] expected: Vec<TimeRange>) {
+        assert_eq!(a.difference(&b).ranges(), expected);
+    }
+
+    #[rstest]
+    #[case(set(&[((22, 0), (6, 0))]), (23, 30), true)]
+    #[case(set(&[((22, 0), (6, 0))]), (0, 0), true)]
+    #[case(set(&[((22, 0), (6, 0))]), (6, 0), false)]
+    #[case(set(&[((9, 0), (17, 0))]), (9, 0), true)]
+    fn test_contains(#[case] set: TimeRangeSet, #[case] time: (u32, u32), #[case] expected: bool) {
+        assert_eq!(
+            set.contains(NaiveTime::from_hms_opt(time.0, time.1, 0).unwrap()),
+            expected
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod zone;
+pub use zone::*;
+
+mod posix_tz;
+mod tzif;
@@ -0,0 +1,340 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+const SECONDS_PER_HOUR: i32 = 3_600;
+const SECONDS_PER_DAY: i64 = 86_400;
+const DEFAULT_TRANSITION_TIME: i32 = 2 * SECONDS_PER_HOUR;
+
+/// A POSIX TZ string such as `EST5EDT,M3.2.0,M11.1.0`, as found in the footer of
+/// TZif version 2+ files. Offsets are stored as seconds east of UTC.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PosixTz {
+    std_offset: i32,
+    dst: Option<DstRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DstRule {
+    offset: i32,
+    start: RuleDate,
+    start_time: i32,
+    end: RuleDate,
+    end_time: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleDate {
+    /// `Jn`: day 1 to 365, February 29th is never counted.
+    JulianNoLeap(u32),
+    /// `n`: day 0 to 365, February 29th is counted in leap years.
+    JulianWithLeap(u32),
+    /// `Mm.w.d`: day `d` (0 = Sunday) of week `w` (5 = last) of month `m`.
+    MonthWeekDay { month: u32, week: u32, weekday: u32 },
+}
+
+/// Check if an offset is less than a day either way, as `FixedOffset` requires.
+pub(crate) fn is_valid_offset(offset: i32) -> bool {
+    (offset as i64).abs() < SECONDS_PER_DAY
+}
+
+impl PosixTz {
+    pub(crate) fn fixed(offset_seconds: i32) -> Self {
+        Self {
+            std_offset: offset_seconds,
+            dst: None,
+        }
+    }
+
+    pub(crate) fn parse(spec: &str) -> Option<Self> {
+        let mut parser = Parser { input: spec.as_bytes(), pos: 0 };
+
+        parser.name()?;
+        let std_offset = -parser.offset()?;
+        if !is_valid_offset(std_offset) {
+            return None;
+        }
+
+        if parser.at_end() {
+            return Some(Self::fixed(std_offset));
+        }
+
+        parser.name()?;
+        let dst_offset = match parser.peek() {
+            Some(b',') | None => std_offset.checked_add(SECONDS_PER_HOUR)?,
+            _ => -parser.offset()?,
+        };
+        if !is_valid_offset(dst_offset) {
+            return None;
+        }
+
+        // POSIX leaves the default rule implementation defined; use the US rule like glibc.
+        let (start, start_time, end, end_time) = if parser.at_end() {
+            (
+                RuleDate::MonthWeekDay { month: 3, week: 2, weekday: 0 },
+                DEFAULT_TRANSITION_TIME,
+                RuleDate::MonthWeekDay { month: 11, week: 1, weekday: 0 },
+                DEFAULT_TRANSITION_TIME,
+            )
+        } else {
+            parser.expect(b',')?;
+            let (start, start_time) = parser.rule()?;
+            parser.expect(b',')?;
+            let (end, end_time) = parser.rule()?;
+            (start, start_time, end, end_time)
+        };
+
+        if !parser.at_end() {
+            return None;
+        }
+
+        Some(Self {
+            std_offset,
+            dst: Some(DstRule {
+                offset: dst_offset,
+                start,
+                start_time,
+                end,
+                end_time,
+            }),
+        })
+    }
+
+    /// Get the offset in effect at the UTC timestamp.
+    pub(crate) fn offset_at(&self, timestamp: i64) -> i32 {
+        let rule = match &self.dst {
+            None => return self.std_offset,
+            Some(rule) => rule,
+        };
+
+        let local_days = (timestamp + self.std_offset as i64).div_euclid(SECONDS_PER_DAY);
+        let year = match NaiveDate::from_num_days_from_ce_opt(local_days as i32 + EPOCH_DAYS_FROM_CE) {
+            Some(date) => date.year(),
+            None => return self.std_offset,
+        };
+
+        let (start, end) = match (
+            transition(year, rule.start, rule.start_time, self.std_offset),
+            transition(year, rule.end, rule.end_time, rule.offset),
+        ) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return self.std_offset,
+        };
+
+        let in_dst = if start < end {
+            timestamp >= start && timestamp < end
+        } else {
+            timestamp < end || timestamp >= start
+        };
+
+        if in_dst {
+            rule.offset
+        } else {
+            self.std_offset
+        }
+    }
+}
+
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Get the UTC timestamp of a rule transition in `year`, where the rule's local time is
+/// expressed in the offset in effect before the transition.
+fn transition(year: i32, date: RuleDate, time: i32, offset_before: i32) -> Option<i64> {
+    let day = rule_day(year, date)?;
+    let midnight = (day.num_days_from_ce() - EPOCH_DAYS_FROM_CE) as i64 * SECONDS_PER_DAY;
+    Some(midnight + time as i64 - offset_before as i64)
+}
+
+fn rule_day(year: i32, date: RuleDate) -> Option<NaiveDate> {
+    let jan_1 = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    match date {
+        RuleDate::JulianNoLeap(day) => {
+            let leap_adjust = if jan_1.leap_year() && day >= 60 { 1 } else { 0 };
+            Some(jan_1 + Duration::days((day - 1 + leap_adjust) as i64))
+        }
+        RuleDate::JulianWithLeap(day) => Some(jan_1 + Duration::days(day as i64)),
+        RuleDate::MonthWeekDay { month, week, weekday } => {
+            let weekday = Weekday::try_from(((weekday + 6) % 7) as u8).ok()?;
+            if week == 5 {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, 5)
+                    .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+            } else {
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, week as u8)
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn name(&mut self) -> Option<()> {
+        if self.peek() == Some(b'<') {
+            self.pos += 1;
+            while self.peek()? != b'>' {
+                self.pos += 1;
+            }
+            self.pos += 1;
+            return Some(());
+        }
+
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        if self.pos - start >= 3 {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn number(&mut self) -> Option<i32> {
+        let start = self.pos;
+        let mut value: i32 = 0;
+        while let Some(digit) = self.peek().filter(|b| b.is_ascii_digit()) {
+            value = value.checked_mul(10)?.checked_add((digit - b'0') as i32)?;
+            self.pos += 1;
+        }
+        if self.pos > start {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Parse `[+|-]hh[:mm[:ss]]` as seconds.
+    fn offset(&mut self) -> Option<i32> {
+        let sign = match self.peek() {
+            Some(b'-') => {
+                self.pos += 1;
+                -1
+            }
+            Some(b'+') => {
+                self.pos += 1;
+                1
+            }
+            _ => 1,
+        };
+
+        let mut seconds = self.number()?.checked_mul(SECONDS_PER_HOUR)?;
+        if self.expect(b':').is_some() {
+            seconds = seconds.checked_add(self.number()?.checked_mul(60)?)?;
+            if self.expect(b':').is_some() {
+                seconds = seconds.checked_add(self.number()?)?;
+            }
+        }
+        Some(sign * seconds)
+    }
+
+    fn rule(&mut self) -> Option<(RuleDate, i32)> {
+        let date = match self.peek()? {
+            b'J' => {
+                self.pos += 1;
+                let day = self.number()? as u32;
+                if !(1..=365).contains(&day) {
+                    return None;
+                }
+                RuleDate::JulianNoLeap(day)
+            }
+            b'M' => {
+                self.pos += 1;
+                let month = self.number()? as u32;
+                self.expect(b'.')?;
+                let week = self.number()? as u32;
+                self.expect(b'.')?;
+                let weekday = self.number()? as u32;
+                if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+                    return None;
+                }
+                RuleDate::MonthWeekDay { month, week, weekday }
+            }
+            _ => {
+                let day = self.number()? as u32;
+                if day > 365 {
+                    return None;
+                }
+                RuleDate::JulianWithLeap(day)
+            }
+        };
+
+        let time = if self.expect(b'/').is_some() {
+            self.offset()?
+        } else {
+            DEFAULT_TRANSITION_TIME
+        };
+
+        Some((date, time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn timestamp(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> i64 {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    #[rstest]
+    #[case("EST5EDT,M3.2.0,M11.1.0", timestamp(2025, 3, 9, 6, 59), -5 * 3_600)]
+    #[case("EST5EDT,M3.2.0,M11.1.0", timestamp(2025, 3, 9, 7, 0), -4 * 3_600)]
+    #[case("EST5EDT,M3.2.0,M11.1.0", timestamp(2025, 11, 2, 5, 59), -4 * 3_600)]
+    #[case("EST5EDT,M3.2.0,M11.1.0", timestamp(2025, 11, 2, 6, 0), -5 * 3_600)]
+    #[case("EST5EDT", timestamp(2025, 7, 1, 0, 0), -4 * 3_600)]
+    #[case("CET-1CEST,M3.5.0,M10.5.0/3", timestamp(2025, 3, 30, 0, 59), 3_600)]
+    #[case("CET-1CEST,M3.5.0,M10.5.0/3", timestamp(2025, 3, 30, 1, 0), 2 * 3_600)]
+    #[case("CET-1CEST,M3.5.0,M10.5.0/3", timestamp(2025, 10, 26, 1, 0), 3_600)]
+    #[case("AEST-10AEDT,M10.1.0,M4.1.0/3", timestamp(2025, 1, 15, 0, 0), 11 * 3_600)]
+    #[case("AEST-10AEDT,M10.1.0,M4.1.0/3", timestamp(2025, 7, 15, 0, 0), 10 * 3_600)]
+    #[case("<+0530>-5:30", timestamp(2025, 7, 15, 0, 0), 5 * 3_600 + 1_800)]
+    #[case("<-03>3", timestamp(2025, 7, 15, 0, 0), -3 * 3_600)]
+    fn test_offset_at(#[case] spec: &str, #[case] timestamp: i64, #[case] expected_offset: i32) {
+        let tz = PosixTz::parse(spec).unwrap();
+        assert_eq!(tz.offset_at(timestamp), expected_offset, "Failed for {} at {}", spec, timestamp);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("E5")]
+    #[case("EST")]
+    #[case("EST5EDT,M3.2.0")]
+    #[case("EST5EDT,M13.2.0,M11.1.0")]
+    #[case("<EST5")]
+    #[case("EST999999")]
+    #[case("EST5:99999999")]
+    #[case("EST5EDT,M3.2.0/999999,M11.1.0")]
+    #[case("EST-596523EDT")]
+    #[case("EST24")]
+    #[case("EST25")]
+    #[case("<X>-30")]
+    #[case("EST5EDT-24")]
+    #[case("X-23:30Y")]
+    fn test_parse_invalid(#[case] spec: &str) {
+        assert_eq!(PosixTz::parse(spec), None);
+    }
+}
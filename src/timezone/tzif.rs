@@ -0,0 +1,161 @@
+use crate::timezone::posix_tz::{is_valid_offset, PosixTz};
+
+/// Transition data parsed from a TZif file (RFC 8536).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Tzif {
+    /// UTC timestamps at which the offset changes, in ascending order.
+    pub(crate) transitions: Vec<i64>,
+    /// The offset, in seconds east of UTC, in effect from each transition onward.
+    pub(crate) offsets: Vec<i32>,
+    /// The offset in effect before the first transition.
+    pub(crate) initial_offset: i32,
+    /// The rule for timestamps after the last transition, from the version 2+ footer.
+    pub(crate) footer: Option<PosixTz>,
+}
+
+struct Header {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+const HEADER_LEN: usize = 44;
+
+impl Header {
+    fn parse(data: &[u8]) -> Result<Self, String> {
+        if data.len() < HEADER_LEN || &data[0..4] != b"TZif" {
+            return Err("missing TZif header".to_string());
+        }
+
+        let count = |index: usize| {
+            let start = 20 + index * 4;
+            u32::from_be_bytes([data[start], data[start + 1], data[start + 2], data[start + 3]]) as usize
+        };
+
+        Ok(Self {
+            version: data[4],
+            isutcnt: count(0),
+            isstdcnt: count(1),
+            leapcnt: count(2),
+            timecnt: count(3),
+            typecnt: count(4),
+            charcnt: count(5),
+        })
+    }
+
+    fn data_len(&self, time_size: usize) -> usize {
+        self.timecnt * time_size
+            + self.timecnt
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+}
+
+impl Tzif {
+    pub(crate) fn parse(data: &[u8]) -> Result<Self, String> {
+        let header = Header::parse(data)?;
+        let v1_len = HEADER_LEN + header.data_len(4);
+
+        if header.version == 0 {
+            return Self::parse_block(&header, &data[HEADER_LEN..], 4, None);
+        }
+
+        // Version 2+ files repeat the data with 64-bit times, followed by a footer.
+        let data = data.get(v1_len..).ok_or("truncated version 1 data block")?;
+        let header = Header::parse(data)?;
+        let block_len = header.data_len(8);
+        let block = data.get(HEADER_LEN..HEADER_LEN + block_len).ok_or("truncated data block")?;
+        let footer = parse_footer(&data[HEADER_LEN + block_len..])?;
+
+        Self::parse_block(&header, block, 8, footer)
+    }
+
+    fn parse_block(header: &Header, block: &[u8], time_size: usize, footer: Option<PosixTz>) -> Result<Self, String> {
+        if header.typecnt == 0 {
+            return Err("no local time types".to_string());
+        }
+        if block.len() < header.data_len(time_size) {
+            return Err("truncated data block".to_string());
+        }
+
+        let mut pos = 0;
+        let mut transitions = Vec::with_capacity(header.timecnt);
+        for _ in 0..header.timecnt {
+            let bytes = &block[pos..pos + time_size];
+            transitions.push(if time_size == 8 {
+                i64::from_be_bytes(bytes.try_into().unwrap())
+            } else {
+                i32::from_be_bytes(bytes.try_into().unwrap()) as i64
+            });
+            pos += time_size;
+        }
+
+        let type_indices = &block[pos..pos + header.timecnt];
+        pos += header.timecnt;
+
+        let mut type_offsets = Vec::with_capacity(header.typecnt);
+        for _ in 0..header.typecnt {
+            type_offsets.push(i32::from_be_bytes(block[pos..pos + 4].try_into().unwrap()));
+            pos += 6;
+        }
+
+        if !type_offsets.iter().all(|offset| is_valid_offset(*offset)) {
+            return Err("local time type offset is a day or more".to_string());
+        }
+
+        let offsets = type_indices
+            .iter()
+            .map(|index| type_offsets.get(*index as usize).copied().ok_or("invalid local time type index"))
+            .collect::<Result<Vec<i32>, &str>>()?;
+
+        if transitions.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("transitions are not in ascending order".to_string());
+        }
+
+        Ok(Self {
+            transitions,
+            offsets,
+            initial_offset: type_offsets[0],
+            footer,
+        })
+    }
+
+    /// Get the offset in effect at the UTC timestamp.
+    pub(crate) fn offset_at(&self, timestamp: i64) -> i32 {
+        let count = self.transitions.partition_point(|t| *t <= timestamp);
+        if count == self.transitions.len() {
+            if let Some(footer) = &self.footer {
+                return footer.offset_at(timestamp);
+            }
+        }
+
+        if count == 0 {
+            self.initial_offset
+        } else {
+            self.offsets[count - 1]
+        }
+    }
+}
+
+fn parse_footer(data: &[u8]) -> Result<Option<PosixTz>, String> {
+    let text = std::str::from_utf8(data).map_err(|_| "footer is not valid UTF-8")?;
+    let spec = text
+        .strip_prefix('\n')
+        .and_then(|rest| rest.split('\n').next())
+        .unwrap_or("");
+
+    if spec.is_empty() {
+        return Ok(None);
+    }
+
+    PosixTz::parse(spec)
+        .map(Some)
+        .ok_or_else(|| format!("invalid footer TZ string '{}'", spec))
+}
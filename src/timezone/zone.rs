@@ -0,0 +1,408 @@
+use crate::timezone::posix_tz::PosixTz;
+use crate::timezone::tzif::Tzif;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The directory the system zoneinfo database is loaded from.
+pub const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// A time zone, either loaded from the zoneinfo database or with a fixed offset.
+///
+/// Cloning a `Zone` is cheap, the transition data is shared.
+#[derive(Debug, Clone)]
+pub struct Zone {
+    name: String,
+    rules: Arc<Rules>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Rules {
+    Tzif(Tzif),
+    Posix(PosixTz),
+}
+
+/// The ways a local date-time can map onto a zone's timeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalResolution {
+    /// The local time occurs exactly once.
+    Single(DateTime<FixedOffset>),
+    /// The local time occurs twice because clocks were turned back (a fold).
+    /// Holds the earlier and the later instant.
+    Ambiguous(DateTime<FixedOffset>, DateTime<FixedOffset>),
+    /// The local time was skipped because clocks were turned forward (a gap).
+    /// Holds the instants obtained by shifting backward and forward by the gap length.
+    Gap(DateTime<FixedOffset>, DateTime<FixedOffset>),
+}
+
+/// How to resolve a local time that falls in a gap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GapPolicy {
+    /// Reject the local time.
+    Error,
+    /// Shift forward by the length of the gap, so 02:30 becomes 03:30.
+    #[default]
+    ShiftForward,
+    /// Shift backward by the length of the gap, so 02:30 becomes 01:30.
+    ShiftBackward,
+}
+
+/// How to resolve a local time that falls in a fold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FoldPolicy {
+    /// Reject the local time.
+    Error,
+    /// Use the earlier of the two instants.
+    #[default]
+    Earlier,
+    /// Use the later of the two instants.
+    Later,
+}
+
+/// The policies used when converting local date-times to instants in a `Zone`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LocalTimePolicy {
+    gap: GapPolicy,
+    fold: FoldPolicy,
+}
+
+impl LocalTimePolicy {
+    pub fn of(gap: GapPolicy, fold: FoldPolicy) -> Self {
+        Self { gap, fold }
+    }
+
+    /// A policy that rejects local times in gaps and folds.
+    pub fn strict() -> Self {
+        Self::of(GapPolicy::Error, FoldPolicy::Error)
+    }
+
+    pub fn gap(&self) -> GapPolicy {
+        self.gap
+    }
+
+    pub fn fold(&self) -> FoldPolicy {
+        self.fold
+    }
+}
+
+/// Errors from loading zones or resolving local times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneError {
+    /// The zone name is not a valid zoneinfo name.
+    InvalidName(String),
+    /// The zone could not be read.
+    NotFound(String),
+    /// The zone data or TZ string could not be parsed.
+    InvalidData(String),
+    /// The local time falls in a gap and the policy rejects it.
+    NonexistentLocalTime(NaiveDateTime),
+    /// The local time falls in a fold and the policy rejects it.
+    AmbiguousLocalTime(NaiveDateTime),
+}
+
+impl fmt::Display for ZoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZoneError::InvalidName(name) => write!(f, "invalid time zone name '{}'", name),
+            ZoneError::NotFound(name) => write!(f, "time zone '{}' not found", name),
+            ZoneError::InvalidData(message) => write!(f, "invalid time zone data: {}", message),
+            ZoneError::NonexistentLocalTime(local) => write!(f, "local time {} does not exist", local),
+            ZoneError::AmbiguousLocalTime(local) => write!(f, "local time {} is ambiguous", local),
+        }
+    }
+}
+
+impl Error for ZoneError {}
+
+impl Zone {
+    /// The UTC zone.
+    pub fn utc() -> Self {
+        Self {
+            name: "UTC".to_string(),
+            rules: Arc::new(Rules::Posix(PosixTz::fixed(0))),
+        }
+    }
+
+    /// A zone with a fixed offset from UTC.
+    pub fn fixed(offset: FixedOffset) -> Self {
+        Self {
+            name: offset.to_string(),
+            rules: Arc::new(Rules::Posix(PosixTz::fixed(offset.local_minus_utc()))),
+        }
+    }
+
+    /// Load a zone such as `America/New_York` from the system zoneinfo database.
+    pub fn load(name: &str) -> Result<Self, ZoneError> {
+        let valid = !name.is_empty()
+            && !name.starts_with('/')
+            && name.split('/').all(|part| !part.is_empty() && part != "." && part != "..")
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || "/_-+".contains(c));
+        if !valid {
+            return Err(ZoneError::InvalidName(name.to_string()));
+        }
+
+        Self::load_file(name, Path::new(ZONEINFO_DIR).join(name))
+    }
+
+    /// Load a zone from a TZif file.
+    pub fn load_file(name: &str, path: impl AsRef<Path>) -> Result<Self, ZoneError> {
+        let data = std::fs::read(path).map_err(|_| ZoneError::NotFound(name.to_string()))?;
+        Self::from_tzif(name, &data)
+    }
+
+    /// Create a zone from the contents of a TZif file.
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<Self, ZoneError> {
+        let tzif = Tzif::parse(data).map_err(ZoneError::InvalidData)?;
+        Ok(Self {
+            name: name.to_string(),
+            rules: Arc::new(Rules::Tzif(tzif)),
+        })
+    }
+
+    /// Create a zone from a POSIX TZ string such as `EST5EDT,M3.2.0,M11.1.0`.
+    pub fn from_posix_tz(spec: &str) -> Result<Self, ZoneError> {
+        let tz = PosixTz::parse(spec).ok_or_else(|| ZoneError::InvalidData(format!("invalid TZ string '{}'", spec)))?;
+        Ok(Self {
+            name: spec.to_string(),
+            rules: Arc::new(Rules::Posix(tz)),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the offset from UTC in effect at the UTC date-time.
+    pub fn offset_at_utc(&self, utc: NaiveDateTime) -> FixedOffset {
+        FixedOffset::east_opt(self.offset_seconds(utc.and_utc().timestamp())).unwrap()
+    }
+
+    /// Convert a UTC date-time to an instant with this zone's offset.
+    pub fn from_utc(&self, utc: NaiveDateTime) -> DateTime<FixedOffset> {
+        DateTime::from_naive_utc_and_offset(utc, self.offset_at_utc(utc))
+    }
+
+    /// Work out where a local date-time falls on this zone's timeline.
+    pub fn resolve_local(&self, local: NaiveDateTime) -> LocalResolution {
+        let local_seconds = local.and_utc().timestamp();
+
+        // Offsets never change twice within a couple of days, so the offsets either side
+        // of the local time are the only candidates.
+        let offset_before = self.offset_seconds(local_seconds - SECONDS_PER_DAY);
+        let offset_after = self.offset_seconds(local_seconds + SECONDS_PER_DAY);
+        let to_utc = |offset: i32| local - Duration::seconds(offset as i64);
+        let is_valid = |offset: i32| self.offset_seconds(local_seconds - offset as i64) == offset;
+
+        match (is_valid(offset_before), is_valid(offset_after)) {
+            (true, true) if offset_before != offset_after => {
+                let first = self.from_utc(to_utc(offset_before));
+                let second = self.from_utc(to_utc(offset_after));
+                LocalResolution::Ambiguous(first.min(second), first.max(second))
+            }
+            (true, _) => LocalResolution::Single(self.from_utc(to_utc(offset_before))),
+            (false, true) => LocalResolution::Single(self.from_utc(to_utc(offset_after))),
+            (false, false) => {
+                LocalResolution::Gap(self.from_utc(to_utc(offset_after)), self.from_utc(to_utc(offset_before)))
+            }
+        }
+    }
+
+    /// Convert a local date-time to an instant, resolving gaps and folds with the policy.
+    pub fn from_local(&self, local: NaiveDateTime, policy: LocalTimePolicy) -> Result<DateTime<FixedOffset>, ZoneError> {
+        match self.resolve_local(local) {
+            LocalResolution::Single(instant) => Ok(instant),
+            LocalResolution::Ambiguous(earlier, later) => match policy.fold() {
+                FoldPolicy::Earlier => Ok(earlier),
+                FoldPolicy::Later => Ok(later),
+                FoldPolicy::Error => Err(ZoneError::AmbiguousLocalTime(local)),
+            },
+            LocalResolution::Gap(backward, forward) => match policy.gap() {
+                GapPolicy::ShiftForward => Ok(forward),
+                GapPolicy::ShiftBackward => Ok(backward),
+                GapPolicy::Error => Err(ZoneError::NonexistentLocalTime(local)),
+            },
+        }
+    }
+
+    /// Get the instant of midnight on the local date. If midnight was skipped by a transition,
+    /// it is shifted forward by the length of the gap as with `GapPolicy::ShiftForward`. That
+    /// is the end of the gap only when the gap starts exactly at midnight.
+    pub fn start_of_day(&self, date: NaiveDate) -> DateTime<FixedOffset> {
+        let policy = LocalTimePolicy::of(GapPolicy::ShiftForward, FoldPolicy::Earlier);
+        self.from_local(date.and_time(NaiveTime::MIN), policy).unwrap()
+    }
+
+    /// Get the elapsed length of the local date, e.g. 23 or 25 hours on transition days.
+    pub fn day_length(&self, date: NaiveDate) -> Duration {
+        self.start_of_day(date + Duration::days(1)) - self.start_of_day(date)
+    }
+
+    fn offset_seconds(&self, timestamp: i64) -> i32 {
+        match self.rules.as_ref() {
+            Rules::Tzif(tzif) => tzif.offset_at(timestamp),
+            Rules::Posix(tz) => tz.offset_at(timestamp),
+        }
+    }
+}
+
+impl PartialEq for Zone {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.rules == other.rules
+    }
+}
+
+impl Eq for Zone {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rstest::rstest;
+
+    fn local(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn new_york() -> Zone {
+        Zone::from_posix_tz("EST5EDT,M3.2.0,M11.1.0").unwrap()
+    }
+
+    #[rstest]
+    #[case(local(3, 9, 2, 30), GapPolicy::ShiftForward, "2025-03-09T03:30:00-04:00")]
+    #[case(local(3, 9, 2, 30), GapPolicy::ShiftBackward, "2025-03-09T01:30:00-05:00")]
+    #[case(local(3, 9, 1, 59), GapPolicy::Error, "2025-03-09T01:59:00-05:00")]
+    #[case(local(3, 9, 3, 0), GapPolicy::Error, "2025-03-09T03:00:00-04:00")]
+    fn test_from_local_gap(#[case] local: NaiveDateTime, #[case] gap: GapPolicy, #[case] expected: &str) {
+        let result = new_york().from_local(local, LocalTimePolicy::of(gap, FoldPolicy::Error)).unwrap();
+        assert_eq!(result, DateTime::parse_from_rfc3339(expected).unwrap());
+    }
+
+    #[rstest]
+    #[case(FoldPolicy::Earlier, "2025-11-02T01:30:00-04:00")]
+    #[case(FoldPolicy::Later, "2025-11-02T01:30:00-05:00")]
+    fn test_from_local_fold(#[case] fold: FoldPolicy, #[case] expected: &str) {
+        let result = new_york().from_local(local(11, 2, 1, 30), LocalTimePolicy::of(GapPolicy::Error, fold)).unwrap();
+        assert_eq!(result, DateTime::parse_from_rfc3339(expected).unwrap());
+    }
+
+    #[test]
+    fn test_strict_policy_rejects_gaps_and_folds() {
+        let zone = new_york();
+        assert_eq!(
+            zone.from_local(local(3, 9, 2, 30), LocalTimePolicy::strict()),
+            Err(ZoneError::NonexistentLocalTime(local(3, 9, 2, 30)))
+        );
+        assert_eq!(
+            zone.from_local(local(11, 2, 1, 30), LocalTimePolicy::strict()),
+            Err(ZoneError::AmbiguousLocalTime(local(11, 2, 1, 30)))
+        );
+    }
+
+    #[rstest]
+    #[case(NaiveDate::from_ymd_opt(2025, 3, 8).unwrap(), 24)]
+    #[case(NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(), 23)]
+    #[case(NaiveDate::from_ymd_opt(2025, 11, 2).unwrap(), 25)]
+    fn test_day_length(#[case] date: NaiveDate, #[case] expected_hours: i64) {
+        assert_eq!(new_york().day_length(date), Duration::hours(expected_hours));
+    }
+
+    #[test]
+    fn test_start_of_day_when_midnight_is_skipped() {
+        // Clocks go from 00:00 to 01:00 at the start of DST
+        let zone = Zone::from_posix_tz("<-03>3<-02>,M3.5.0/0,M10.5.0/1").unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 3, 30).unwrap();
+        assert_eq!(zone.start_of_day(date), DateTime::parse_from_rfc3339("2025-03-30T01:00:00-02:00").unwrap());
+        assert_eq!(zone.day_length(date), Duration::hours(23));
+    }
+
+    #[test]
+    fn test_fixed() {
+        let zone = Zone::fixed(FixedOffset::east_opt(5 * 3_600 + 1_800).unwrap());
+        assert_eq!(zone.name(), "+05:30");
+        assert_eq!(
+            zone.resolve_local(local(3, 9, 2, 30)),
+            LocalResolution::Single(DateTime::parse_from_rfc3339("2025-03-09T02:30:00+05:30").unwrap())
+        );
+    }
+
+    #[rstest]
+    #[case("EST25")]
+    #[case("<X>-30")]
+    #[case("EST5EDT-24")]
+    fn test_from_posix_tz_rejects_day_long_offsets(#[case] spec: &str) {
+        assert_eq!(
+            Zone::from_posix_tz(spec).unwrap_err(),
+            ZoneError::InvalidData(format!("invalid TZ string '{}'", spec))
+        );
+    }
+
+    #[rstest]
+    #[case(86_400, false)]
+    #[case(-86_400, false)]
+    #[case(86_399, true)]
+    fn test_from_tzif_offset_range(#[case] offset: i32, #[case] valid: bool) {
+        // A version 1 file with a single local time type and no transitions
+        let mut data = b"TZif".to_vec();
+        data.resize(20, 0);
+        for count in [0u32, 0, 0, 0, 1, 4] {
+            data.extend(count.to_be_bytes());
+        }
+        data.extend(offset.to_be_bytes());
+        data.extend([0, 0]);
+        data.extend(b"XXX\0");
+
+        match Zone::from_tzif("Test", &data) {
+            Ok(zone) => {
+                assert!(valid);
+                assert_eq!(zone.offset_at_utc(local(1, 1, 0, 0)).local_minus_utc(), offset);
+            }
+            Err(error) => {
+                assert!(!valid);
+                assert_eq!(
+                    error,
+                    ZoneError::InvalidData("local time type offset is a day or more".to_string())
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("/etc/passwd")]
+    #[case("../etc/passwd")]
+    #[case("America//New_York")]
+    #[case("America/New York")]
+    fn test_load_invalid_name(#[case] name: &str) {
+        assert_eq!(Zone::load(name).unwrap_err(), ZoneError::InvalidName(name.to_string()));
+    }
+
+    #[test]
+    fn test_load_system_zone() {
+        // The zoneinfo database isn't installed everywhere
+        if !Path::new(ZONEINFO_DIR).join("America/New_York").exists() {
+            return;
+        }
+
+        let zone = Zone::load("America/New_York").unwrap();
+        let rule = new_york();
+        let mut utc = local(1, 1, 0, 0);
+        while utc < local(12, 31, 0, 0) {
+            assert_eq!(zone.offset_at_utc(utc), rule.offset_at_utc(utc), "Failed for {}", utc);
+            utc += Duration::minutes(30);
+        }
+
+        // 1974 had year-round daylight saving time from January 6th
+        let utc = NaiveDate::from_ymd_opt(1974, 2, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(zone.offset_at_utc(utc), FixedOffset::west_opt(4 * 3_600).unwrap());
+
+        // Far future times use the footer rule
+        let utc = NaiveDate::from_ymd_opt(2100, 7, 1).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        assert_eq!(zone.offset_at_utc(utc), FixedOffset::west_opt(4 * 3_600).unwrap());
+
+        assert_eq!(Zone::load("Not/A_Zone").unwrap_err(), ZoneError::NotFound("Not/A_Zone".to_string()));
+    }
+}
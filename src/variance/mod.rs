@@ -1,2 +1,4 @@
 pub mod variance;
 pub use variance::*;
+pub mod variance_thresholds;
+pub use variance_thresholds::*;
@@ -1,49 +1,354 @@
+use crate::numbers::{Currency, Money, Percent};
+use bigdecimal::{BigDecimal, Signed, ToPrimitive, Zero};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VarianceError {
+    /// The difference does not fit in the value type.
+    Overflow,
+    CurrencyMismatch(Currency, Currency),
+    /// A float variance is NaN or infinite.
+    NotFinite,
+}
+
+impl fmt::Display for VarianceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VarianceError::Overflow => write!(f, "the variance is too large for its value type"),
+            VarianceError::CurrencyMismatch(left, right) => {
+                write!(f, "cannot take a variance between amounts in {} and {}", left, right)
+            }
+            VarianceError::NotFinite => write!(f, "the variance is not a finite number"),
+        }
+    }
+}
+
+impl std::error::Error for VarianceError {}
+
+/// A value that a `Variance` can be taken between.
+pub trait VarianceValue: Clone + PartialEq {
+    /// The type percent variances are calculated in.
+    type Percent: Clone + PartialOrd;
+
+    fn is_zero_value(&self) -> bool;
+
+    /// Get `self - other`, or an error if it cannot be taken.
+    fn checked_difference(&self, other: &Self) -> Result<Self, VarianceError>;
+
+    /// Get `(self - base) / base * 100`. `base` is never zero.
+    fn percent_change(&self, base: &Self) -> Self::Percent;
+
+    /// Get a whole number percent such as -100 or 100.
+    fn whole_percent(percent: i32) -> Self::Percent;
+
+    fn percent_as_f64(percent: &Self::Percent) -> f64;
+
+    /// Get the percent as a `Percent`, or `None` if it is not a finite number.
+    fn to_percent(percent: &Self::Percent) -> Option<Percent>;
+
+    /// Get a `Percent` in the type percent variances are calculated in.
+    fn from_percent(percent: &Percent) -> Self::Percent;
+
+    /// Check if the value is below zero.
+    fn is_negative_value(&self) -> bool;
+
+    /// Check if the value is a finite number. Only floats can fail this.
+    fn is_finite_value(&self) -> bool {
+        true
+    }
+
+    /// Check if the size of the value is more than the size of `threshold`, ignoring signs.
+    fn exceeds(&self, threshold: &Self) -> Result<bool, VarianceError>;
+}
+
+/// A `VarianceValue` whose difference can always be taken, so `Variance::between` cannot fail.
+pub trait InfallibleVarianceValue: VarianceValue {
+    /// Get `self - other`.
+    fn difference(&self, other: &Self) -> Self;
+}
+
+impl VarianceValue for f64 {
+    type Percent = f64;
+
+    fn is_zero_value(&self) -> bool {
+        *self == 0.0
+    }
+
+    fn checked_difference(&self, other: &Self) -> Result<Self, VarianceError> {
+        Ok(self.difference(other))
+    }
+
+    fn percent_change(&self, base: &Self) -> f64 {
+        (self - base) / base * 100.0
+    }
+
+    fn whole_percent(percent: i32) -> f64 {
+        percent as f64
+    }
+
+    fn percent_as_f64(percent: &f64) -> f64 {
+        *percent
+    }
+
+    fn to_percent(percent: &f64) -> Option<Percent> {
+        Percent::from_percent(*percent)
+    }
+
+    fn from_percent(percent: &Percent) -> f64 {
+        percent.as_percent()
+    }
+
+    fn is_negative_value(&self) -> bool {
+        *self < 0.0
+    }
+
+    fn is_finite_value(&self) -> bool {
+        self.is_finite()
+    }
+
+    fn exceeds(&self, threshold: &Self) -> Result<bool, VarianceError> {
+        if !self.is_finite() || !threshold.is_finite() {
+            return Err(VarianceError::NotFinite);
+        }
+        Ok(self.abs() > threshold.abs())
+    }
+}
+
+impl InfallibleVarianceValue for f64 {
+    fn difference(&self, other: &Self) -> Self {
+        self - other
+    }
+}
+
+macro_rules! impl_variance_value_for_integer {
+    ($($integer:ty),*) => {
+        $(
+            /// Percents are calculated in `f64`. A difference that overflows is an error.
+            impl VarianceValue for $integer {
+                type Percent = f64;
+
+                fn is_zero_value(&self) -> bool {
+                    *self == 0
+                }
+
+                fn checked_difference(&self, other: &Self) -> Result<Self, VarianceError> {
+                    self.checked_sub(*other).ok_or(VarianceError::Overflow)
+                }
+
+                fn percent_change(&self, base: &Self) -> f64 {
+                    (*self as f64 - *base as f64) / *base as f64 * 100.0
+                }
+
+                fn whole_percent(percent: i32) -> f64 {
+                    percent as f64
+                }
+
+                fn percent_as_f64(percent: &f64) -> f64 {
+                    *percent
+                }
+
+                fn to_percent(percent: &f64) -> Option<Percent> {
+                    Percent::from_percent(*percent)
+                }
+
+                fn from_percent(percent: &Percent) -> f64 {
+                    percent.as_percent()
+                }
+
+                fn is_negative_value(&self) -> bool {
+                    *self < 0
+                }
+
+                fn exceeds(&self, threshold: &Self) -> Result<bool, VarianceError> {
+                    Ok(self.unsigned_abs() > threshold.unsigned_abs())
+                }
+            }
+        )*
+    };
+}
+
+impl_variance_value_for_integer!(i8, i16, i32, i64, i128, isize);
+
+/// Differences are exact, and percents are calculated in `BigDecimal` to its default
+/// division precision.
+impl VarianceValue for BigDecimal {
+    type Percent = BigDecimal;
+
+    fn is_zero_value(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn checked_difference(&self, other: &Self) -> Result<Self, VarianceError> {
+        Ok(self.difference(other))
+    }
+
+    fn percent_change(&self, base: &Self) -> BigDecimal {
+        (self - base) * BigDecimal::from(100) / base
+    }
+
+    fn whole_percent(percent: i32) -> BigDecimal {
+        BigDecimal::from(percent)
+    }
+
+    fn percent_as_f64(percent: &BigDecimal) -> f64 {
+        percent.to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn to_percent(percent: &BigDecimal) -> Option<Percent> {
+        Some(Percent::from_big_decimal_percent(percent))
+    }
+
+    fn from_percent(percent: &Percent) -> BigDecimal {
+        percent.as_big_decimal_percent()
+    }
+
+    fn is_negative_value(&self) -> bool {
+        self.is_negative()
+    }
+
+    fn exceeds(&self, threshold: &Self) -> Result<bool, VarianceError> {
+        Ok(self.abs() > threshold.abs())
+    }
+}
+
+impl InfallibleVarianceValue for BigDecimal {
+    fn difference(&self, other: &Self) -> Self {
+        self - other
+    }
+}
+
+/// Both amounts must be in the same currency, which `Variance::try_between` checks.
+impl VarianceValue for Money {
+    type Percent = BigDecimal;
+
+    fn is_zero_value(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn checked_difference(&self, other: &Self) -> Result<Self, VarianceError> {
+        if self.currency() != other.currency() {
+            return Err(VarianceError::CurrencyMismatch(self.currency(), other.currency()));
+        }
+        Ok(Money::of(self.amount() - other.amount(), self.currency()))
+    }
+
+    fn percent_change(&self, base: &Self) -> BigDecimal {
+        (self.amount() - base.amount()) * BigDecimal::from(100) / base.amount()
+    }
+
+    fn whole_percent(percent: i32) -> BigDecimal {
+        BigDecimal::from(percent)
+    }
+
+    fn percent_as_f64(percent: &BigDecimal) -> f64 {
+        percent.to_f64().unwrap_or(f64::NAN)
+    }
+
+    fn to_percent(percent: &BigDecimal) -> Option<Percent> {
+        Some(Percent::from_big_decimal_percent(percent))
+    }
+
+    fn from_percent(percent: &Percent) -> BigDecimal {
+        percent.as_big_decimal_percent()
+    }
+
+    fn is_negative_value(&self) -> bool {
+        self.is_negative()
+    }
+
+    fn exceeds(&self, threshold: &Self) -> Result<bool, VarianceError> {
+        if self.currency() != threshold.currency() {
+            return Err(VarianceError::CurrencyMismatch(self.currency(), threshold.currency()));
+        }
+        Ok(self.amount().abs() > threshold.amount().abs())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
-pub struct Variance {
-    value1: f64,
-    value2: f64,
+pub struct Variance<T: VarianceValue = f64> {
+    value1: T,
+    value2: T,
+    absolute: T,
+}
+
+impl<T: InfallibleVarianceValue> Variance<T> {
+    pub fn between(value1: T, value2: T) -> Self {
+        let absolute = value1.difference(&value2);
+        Self {
+            value1,
+            value2,
+            absolute,
+        }
+    }
 }
 
-impl Variance {
-    pub fn between(value1: f64, value2: f64) -> Self {
-        Self { value1, value2 }
+impl<T: VarianceValue> Variance<T> {
+    /// Create a variance between values whose difference may not be representable, such as
+    /// integers that overflow or amounts of money in different currencies.
+    pub fn try_between(value1: T, value2: T) -> Result<Self, VarianceError> {
+        let absolute = value1.checked_difference(&value2)?;
+        Ok(Self {
+            value1,
+            value2,
+            absolute,
+        })
     }
 
-    pub fn as_absolute(&self) -> f64 {
-        self.value1 - self.value2
+    pub fn as_absolute(&self) -> T {
+        self.absolute.clone()
     }
 
-    pub fn as_percent(&self) -> f64 {
-        let v1 = self.value1;
-        let v2 = self.value2;
+    /// Get how much `value1` differs from `value2` as a percent of `value2`. When one of the
+    /// values is zero the variance is -100 (`value1` is zero) or 100 (`value2` is zero), and
+    /// when both are zero it is 0.
+    pub fn as_percent(&self) -> T::Percent {
+        let v1 = &self.value1;
+        let v2 = &self.value2;
 
-        if v1 != 0.0 && v2 != 0.0 && v1 != v2 {
-            (v1 - v2) / v2 * 100.0
-        } else if v1 == 0.0 && v2 == 0.0 {
-            0.0
-        } else if v1 == 0.0 {
-            -100.0
-        } else if v2 == 0.0 {
-            100.0
+        if !v1.is_zero_value() && !v2.is_zero_value() && v1 != v2 {
+            v1.percent_change(v2)
+        } else if v1.is_zero_value() && v2.is_zero_value() {
+            T::whole_percent(0)
+        } else if v1.is_zero_value() {
+            T::whole_percent(-100)
+        } else if v2.is_zero_value() {
+            T::whole_percent(100)
         } else {
-            0.0
+            T::whole_percent(0)
         }
     }
 
+    /// Get the variance as a `Percent`, with the same zero rules as `as_percent`, or `None`
+    /// if it is not a finite number.
+    pub fn percent(&self) -> Option<Percent> {
+        T::to_percent(&self.as_percent())
+    }
+
     pub fn is_outside_allowed_variance_percentages(
         &self,
         allowed_from_variance_percentage: f64,
         allowed_to_variance_percentage: f64,
     ) -> bool {
-        let percent_variance = self.as_percent();
+        let percent_variance = T::percent_as_f64(&self.as_percent());
         percent_variance < -allowed_from_variance_percentage
             || percent_variance > allowed_to_variance_percentage
     }
+
+    /// Check if the variance is below `-allowed_from` or above `allowed_to`. The comparison is
+    /// made in the type percent variances are calculated in, so it agrees with
+    /// `is_outside_allowed_variance_percentages` and is exact for decimals.
+    pub fn is_outside_allowed_percents(&self, allowed_from: &Percent, allowed_to: &Percent) -> bool {
+        let percent_variance = self.as_percent();
+        percent_variance < T::from_percent(&-allowed_from.clone()) || percent_variance > T::from_percent(allowed_to)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::variance::Variance;
+    use crate::numbers::{Currency, Money, Percent};
+    use crate::variance::{Variance, VarianceError};
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
     use rstest::rstest;
 
     #[rstest]
@@ -73,5 +378,126 @@ mod tests {
         assert_eq!(variance.as_absolute(), absolute_variance);
         assert_eq!(variance.as_percent(), percent_variance);
         assert_eq!(variance.is_outside_allowed_variance_percentages(from_variance, to_variance), expected_outside_variance);
+        assert_eq!(variance.percent(), Percent::from_percent(percent_variance));
+        assert_eq!(
+            variance.is_outside_allowed_percents(
+                &Percent::from_percent(from_variance).unwrap(),
+                &Percent::from_percent(to_variance).unwrap()
+            ),
+            expected_outside_variance
+        );
+    }
+
+    #[rstest]
+    #[case(10, 20, -10, -50.0)]
+    #[case(20, 10, 10, 100.0)]
+    #[case(0, 0, 0, 0.0)]
+    #[case(0, 100, -100, -100.0)]
+    #[case(100, 0, 100, 100.0)]
+    #[case(100, 100, 0, 0.0)]
+    #[case(1, 4, -3, -75.0)]
+    fn test_integer_variance(
+        #[case] value1: i64,
+        #[case] value2: i64,
+        #[case] absolute_variance: i64,
+        #[case] percent_variance: f64,
+    ) {
+        let variance = Variance::try_between(value1, value2).unwrap();
+
+        assert_eq!(variance.as_absolute(), absolute_variance);
+        assert_eq!(variance.as_percent(), percent_variance);
+    }
+
+    #[test]
+    fn test_integer_variance_overflow() {
+        assert_eq!(Variance::try_between(100i8, -100i8).err(), Some(VarianceError::Overflow));
+        assert_eq!(Variance::try_between(i64::MIN, 1).err(), Some(VarianceError::Overflow));
+        let variance = Variance::try_between(-100i8, 20i8).unwrap();
+        assert_eq!(variance.as_absolute(), -120);
+        assert_eq!(variance.as_percent(), -600.0);
+    }
+
+    #[rstest]
+    #[case("10.10", "20.20", "-10.10", "-50")]
+    #[case("0.3", "0.1", "0.2", "200")]
+    #[case("0", "0", "0", "0")]
+    #[case("0", "100", "-100", "-100")]
+    #[case("100", "0", "100", "100")]
+    #[case("100.00", "100", "0", "0")]
+    fn test_big_decimal_variance(
+        #[case] value1: &str,
+        #[case] value2: &str,
+        #[case] absolute_variance: &str,
+        #[case] percent_variance: &str,
+    ) {
+        let decimal = |text: &str| BigDecimal::from_str(text).unwrap();
+        let variance = Variance::between(decimal(value1), decimal(value2));
+
+        assert_eq!(variance.as_absolute(), decimal(absolute_variance));
+        assert_eq!(variance.as_percent(), decimal(percent_variance));
+    }
+
+    #[test]
+    fn test_big_decimal_variance_is_exact() {
+        // In f64, (0.3 - 0.1) / 0.1 * 100 is 199.99999999999997
+        assert_ne!(Variance::between(0.3, 0.1).as_percent(), 200.0);
+        let variance = Variance::between(BigDecimal::from_str("0.3").unwrap(), BigDecimal::from_str("0.1").unwrap());
+        assert_eq!(variance.percent(), Percent::from_percent(200.0));
+    }
+
+    #[rstest]
+    #[case("129", "100", "29", "29", false)]
+    #[case("129.01", "100", "29", "29", true)]
+    #[case("71", "100", "29", "29", false)]
+    #[case("70.99", "100", "29", "29", true)]
+    #[case("112.345", "100", "0", "12.345", false)]
+    fn test_big_decimal_allowed_percents_boundary(
+        #[case] value1: &str,
+        #[case] value2: &str,
+        #[case] from_variance: &str,
+        #[case] to_variance: &str,
+        #[case] expected_outside_variance: bool,
+    ) {
+        let decimal = |text: &str| BigDecimal::from_str(text).unwrap();
+        let variance = Variance::between(decimal(value1), decimal(value2));
+        let from = Percent::from_big_decimal_percent(&decimal(from_variance));
+        let to = Percent::from_big_decimal_percent(&decimal(to_variance));
+
+        assert_eq!(variance.is_outside_allowed_percents(&from, &to), expected_outside_variance);
+        assert_eq!(
+            variance.is_outside_allowed_variance_percentages(from.as_percent(), to.as_percent()),
+            expected_outside_variance
+        );
+    }
+
+    #[test]
+    fn test_not_finite_percent() {
+        assert_eq!(Variance::between(f64::NAN, 10.0).percent(), None);
+    }
+
+    #[test]
+    fn test_money_variance() {
+        let usd = |cents| Money::from_minor_units(cents, Currency::USD);
+        let variance = Variance::try_between(usd(12_500), usd(10_000)).unwrap();
+
+        assert_eq!(variance.as_absolute(), usd(2_500));
+        assert_eq!(variance.as_percent(), BigDecimal::from(25));
+        assert!(variance.is_outside_allowed_variance_percentages(10.0, 20.0));
+        assert_eq!(
+            Variance::try_between(usd(0), usd(10_000)).unwrap().as_percent(),
+            BigDecimal::from(-100)
+        );
+    }
+
+    #[test]
+    fn test_money_variance_mixed_currencies() {
+        let variance = Variance::try_between(
+            Money::from_minor_units(100, Currency::USD),
+            Money::from_minor_units(100, Currency::EUR),
+        );
+        assert_eq!(
+            variance.err(),
+            Some(VarianceError::CurrencyMismatch(Currency::USD, Currency::EUR))
+        );
     }
 }
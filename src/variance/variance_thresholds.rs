@@ -0,0 +1,337 @@
+use crate::numbers::Percent;
+use crate::variance::{Variance, VarianceError, VarianceValue};
+
+/// How far a variance is from target, from best to worst.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VarianceLevel {
+    #[default]
+    OnTarget,
+    Warning,
+    Critical,
+}
+
+/// Which side of the target a variance is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VarianceDirection {
+    /// `value1` is more than `value2`.
+    Over,
+    /// `value1` is less than `value2`.
+    Under,
+    Even,
+}
+
+/// How a band's percent and absolute thresholds combine. A band with only one of the two
+/// uses that one alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ThresholdPolicy {
+    /// The band is reached when either threshold is exceeded.
+    #[default]
+    Either,
+    /// The band is reached only when both thresholds are exceeded.
+    Both,
+}
+
+/// A level that a variance reaches once its size exceeds the band's thresholds. Thresholds
+/// are sizes, so they are compared with the variance without its sign. The absolute threshold
+/// is in the variance's value type, so decimals and money are compared exactly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianceBand<T: VarianceValue = f64> {
+    level: VarianceLevel,
+    percent: Option<Percent>,
+    absolute: Option<T>,
+}
+
+impl<T: VarianceValue> VarianceBand<T> {
+    pub fn of(level: VarianceLevel, percent: Option<Percent>, absolute: Option<T>) -> Self {
+        Self {
+            level,
+            percent,
+            absolute,
+        }
+    }
+
+    pub fn percent(level: VarianceLevel, percent: Percent) -> Self {
+        Self::of(level, Some(percent), None)
+    }
+
+    pub fn absolute(level: VarianceLevel, absolute: T) -> Self {
+        Self::of(level, None, Some(absolute))
+    }
+
+    pub fn level(&self) -> VarianceLevel {
+        self.level
+    }
+
+    pub fn percent_threshold(&self) -> Option<&Percent> {
+        self.percent.as_ref()
+    }
+
+    pub fn absolute_threshold(&self) -> Option<&T> {
+        self.absolute.as_ref()
+    }
+
+    fn is_reached(&self, percent: &Percent, absolute: &T, policy: ThresholdPolicy) -> Result<bool, VarianceError> {
+        let percent_exceeded = self.percent.as_ref().map(|threshold| percent.abs() > threshold.abs());
+        let absolute_exceeded = self
+            .absolute
+            .as_ref()
+            .map(|threshold| absolute.exceeds(threshold))
+            .transpose()?;
+
+        Ok(match (percent_exceeded, absolute_exceeded, policy) {
+            (Some(percent), Some(absolute), ThresholdPolicy::Either) => percent || absolute,
+            (Some(percent), Some(absolute), ThresholdPolicy::Both) => percent && absolute,
+            (Some(exceeded), None, _) | (None, Some(exceeded), _) => exceeded,
+            (None, None, _) => false,
+        })
+    }
+}
+
+/// The classification of a variance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianceStatus<T: VarianceValue = f64> {
+    level: VarianceLevel,
+    direction: VarianceDirection,
+    percent: Percent,
+    absolute: T,
+}
+
+impl<T: VarianceValue> VarianceStatus<T> {
+    pub fn level(&self) -> VarianceLevel {
+        self.level
+    }
+
+    pub fn direction(&self) -> VarianceDirection {
+        self.direction
+    }
+
+    pub fn percent(&self) -> &Percent {
+        &self.percent
+    }
+
+    pub fn absolute(&self) -> &T {
+        &self.absolute
+    }
+
+    pub fn is_on_target(&self) -> bool {
+        self.level == VarianceLevel::OnTarget
+    }
+}
+
+/// Bands for classifying variances, set separately for being over and under target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VarianceThresholds<T: VarianceValue = f64> {
+    over: Vec<VarianceBand<T>>,
+    under: Vec<VarianceBand<T>>,
+    policy: ThresholdPolicy,
+}
+
+impl<T: VarianceValue> VarianceThresholds<T> {
+    pub fn of(over: Vec<VarianceBand<T>>, under: Vec<VarianceBand<T>>, policy: ThresholdPolicy) -> Self {
+        Self { over, under, policy }
+    }
+
+    /// Use the same bands for both directions.
+    pub fn symmetric(bands: Vec<VarianceBand<T>>, policy: ThresholdPolicy) -> Self {
+        Self::of(bands.clone(), bands, policy)
+    }
+
+    pub fn over(&self) -> &[VarianceBand<T>] {
+        &self.over
+    }
+
+    pub fn under(&self) -> &[VarianceBand<T>] {
+        &self.under
+    }
+
+    pub fn policy(&self) -> ThresholdPolicy {
+        self.policy
+    }
+
+    /// Classify the variance at the worst level whose band it reaches, or on target if it
+    /// reaches none. Percents follow the zero rules of `Variance::as_percent`.
+    ///
+    /// Fails when a float variance is NaN or infinite, or when an amount of money is compared
+    /// with a threshold in another currency.
+    pub fn classify(&self, variance: &Variance<T>) -> Result<VarianceStatus<T>, VarianceError> {
+        let absolute = variance.as_absolute();
+        if !absolute.is_finite_value() {
+            return Err(VarianceError::NotFinite);
+        }
+        let percent = variance.percent().ok_or(VarianceError::NotFinite)?;
+
+        let (direction, bands) = if absolute.is_zero_value() {
+            (VarianceDirection::Even, &self.over)
+        } else if absolute.is_negative_value() {
+            (VarianceDirection::Under, &self.under)
+        } else {
+            (VarianceDirection::Over, &self.over)
+        };
+
+        let mut level = VarianceLevel::default();
+        for band in bands {
+            if band.is_reached(&percent, &absolute, self.policy)? {
+                level = level.max(band.level);
+            }
+        }
+
+        Ok(VarianceStatus {
+            level,
+            direction,
+            percent,
+            absolute,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numbers::{Currency, Money};
+    use bigdecimal::BigDecimal;
+    use rstest::rstest;
+    use std::str::FromStr;
+
+    fn labor_thresholds(policy: ThresholdPolicy) -> VarianceThresholds {
+        VarianceThresholds::of(
+            vec![
+                VarianceBand::of(VarianceLevel::Warning, Percent::from_percent(5.0), Some(2.0)),
+                VarianceBand::of(VarianceLevel::Critical, Percent::from_percent(10.0), Some(4.0)),
+            ],
+            vec![VarianceBand::percent(
+                VarianceLevel::Critical,
+                Percent::from_percent(15.0).unwrap(),
+            )],
+            policy,
+        )
+    }
+
+    #[rstest]
+    #[case(40.0, 40.0, VarianceLevel::OnTarget, VarianceDirection::Even)]
+    #[case(41.0, 40.0, VarianceLevel::OnTarget, VarianceDirection::Over)]
+    #[case(42.5, 40.0, VarianceLevel::Warning, VarianceDirection::Over)]
+    #[case(44.5, 40.0, VarianceLevel::Critical, VarianceDirection::Over)]
+    #[case(21.5, 20.0, VarianceLevel::Warning, VarianceDirection::Over)]
+    #[case(35.0, 40.0, VarianceLevel::OnTarget, VarianceDirection::Under)]
+    #[case(33.0, 40.0, VarianceLevel::Critical, VarianceDirection::Under)]
+    #[case(10.0, 0.0, VarianceLevel::Critical, VarianceDirection::Over)]
+    fn test_classify_either(
+        #[case] actual: f64,
+        #[case] target: f64,
+        #[case] expected_level: VarianceLevel,
+        #[case] expected_direction: VarianceDirection,
+    ) {
+        let status = labor_thresholds(ThresholdPolicy::Either)
+            .classify(&Variance::between(actual, target))
+            .unwrap();
+        assert_eq!(status.level(), expected_level);
+        assert_eq!(status.direction(), expected_direction);
+    }
+
+    #[rstest]
+    #[case(42.5, 40.0, VarianceLevel::Warning)]
+    #[case(21.5, 20.0, VarianceLevel::OnTarget)]
+    #[case(44.5, 40.0, VarianceLevel::Critical)]
+    #[case(102.5, 100.0, VarianceLevel::OnTarget)]
+    #[case(33.0, 40.0, VarianceLevel::Critical)]
+    fn test_classify_both(#[case] actual: f64, #[case] target: f64, #[case] expected_level: VarianceLevel) {
+        let status = labor_thresholds(ThresholdPolicy::Both)
+            .classify(&Variance::between(actual, target))
+            .unwrap();
+        assert_eq!(status.level(), expected_level);
+    }
+
+    #[test]
+    fn test_status_details() {
+        let status = labor_thresholds(ThresholdPolicy::Either)
+            .classify(&Variance::between(44.0, 40.0))
+            .unwrap();
+        assert_eq!(status.absolute(), &4.0);
+        assert_eq!(status.percent(), &Percent::from_percent(10.0).unwrap());
+        assert_eq!(status.level(), VarianceLevel::Warning);
+        assert!(!status.is_on_target());
+    }
+
+    #[test]
+    fn test_symmetric() {
+        let thresholds = VarianceThresholds::symmetric(
+            vec![VarianceBand::absolute(VarianceLevel::Warning, 1.0)],
+            ThresholdPolicy::Either,
+        );
+        assert_eq!(
+            thresholds.classify(&Variance::between(8.0, 10.0)).unwrap().level(),
+            VarianceLevel::Warning
+        );
+        assert_eq!(
+            thresholds.classify(&Variance::between(12.0, 10.0)).unwrap().level(),
+            VarianceLevel::Warning
+        );
+        assert!(thresholds
+            .classify(&Variance::between(10.5, 10.0))
+            .unwrap()
+            .is_on_target());
+    }
+
+    #[rstest]
+    #[case("10.3", "10.2", VarianceLevel::OnTarget)]
+    #[case("10.31", "10.2", VarianceLevel::Warning)]
+    #[case("129", "100", VarianceLevel::Warning)]
+    #[case("129.01", "100", VarianceLevel::Critical)]
+    #[case("10.1", "10.2", VarianceLevel::OnTarget)]
+    #[case("10.09", "10.2", VarianceLevel::Warning)]
+    fn test_classify_big_decimal_boundaries(
+        #[case] actual: &str,
+        #[case] target: &str,
+        #[case] expected_level: VarianceLevel,
+    ) {
+        let decimal = |text: &str| BigDecimal::from_str(text).unwrap();
+        let thresholds = VarianceThresholds::symmetric(
+            vec![
+                VarianceBand::absolute(VarianceLevel::Warning, decimal("0.1")),
+                VarianceBand::percent(VarianceLevel::Critical, Percent::from_percent(29.0).unwrap()),
+            ],
+            ThresholdPolicy::Either,
+        );
+        let status = thresholds
+            .classify(&Variance::between(decimal(actual), decimal(target)))
+            .unwrap();
+        assert_eq!(status.level(), expected_level);
+    }
+
+    #[test]
+    fn test_classify_money() {
+        let usd = |cents| Money::from_minor_units(cents, Currency::USD);
+        let thresholds = VarianceThresholds::symmetric(
+            vec![VarianceBand::absolute(VarianceLevel::Warning, usd(10))],
+            ThresholdPolicy::Either,
+        );
+        let classify = |actual, target| thresholds.classify(&Variance::try_between(actual, target).unwrap());
+
+        assert_eq!(
+            classify(usd(1_030), usd(1_020)).unwrap().level(),
+            VarianceLevel::OnTarget
+        );
+        let status = classify(usd(1_031), usd(1_020)).unwrap();
+        assert_eq!(status.level(), VarianceLevel::Warning);
+        assert_eq!(status.absolute(), &usd(11));
+
+        let eur = Money::from_minor_units(1_031, Currency::EUR);
+        assert_eq!(
+            classify(eur.clone(), eur).err(),
+            Some(VarianceError::CurrencyMismatch(Currency::EUR, Currency::USD))
+        );
+    }
+
+    #[rstest]
+    #[case(f64::NAN, 10.0)]
+    #[case(10.0, f64::NAN)]
+    #[case(f64::INFINITY, 10.0)]
+    #[case(f64::INFINITY, f64::INFINITY)]
+    fn test_classify_not_finite(#[case] actual: f64, #[case] target: f64) {
+        let thresholds = labor_thresholds(ThresholdPolicy::Either);
+        assert_eq!(
+            thresholds.classify(&Variance::between(actual, target)).err(),
+            Some(VarianceError::NotFinite)
+        );
+    }
+}